@@ -0,0 +1,234 @@
+use lib::{
+    char_result::CharResult,
+    game_state::GameState,
+    keyboard::{Keyboard, LetterState},
+    multi_game_state::MultiGameState,
+};
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+const KEYBOARD_ROWS: [&str; 3] =
+    ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const RESET: &str = "\x1b[0m";
+const CORRECT: &str = "\x1b[1;30;42m";
+const PRESENT: &str = "\x1b[1;30;43m";
+const ABSENT: &str = "\x1b[1;37;100m";
+const UNKNOWN: &str = "\x1b[1;30;47m";
+
+/// Draws the game as tiles, colored with ANSI escapes when stdout is
+/// a terminal and `NO_COLOR` is not set, as plain text otherwise.
+pub(crate) struct Board {
+    color: bool,
+}
+
+impl Board {
+    pub(crate) fn detect() -> Self {
+        let no_color = env::var_os("NO_COLOR")
+            .is_some_and(|v| !v.is_empty());
+
+        Self {
+            color: io::stdout().is_terminal() && !no_color,
+        }
+    }
+
+    /// Prints the whole board, replacing the previous one on a
+    /// terminal.
+    pub(crate) fn draw(&self, game_state: &GameState) {
+        if self.color {
+            print!("{CLEAR_SCREEN}");
+        }
+
+        println!("{}", self.render(game_state).join("\n"));
+    }
+
+    /// Prints the boards of a multi-board game side by side.
+    pub(crate) fn draw_multi(
+        &self,
+        multi_game_state: &MultiGameState,
+    ) {
+        if self.color {
+            print!("{CLEAR_SCREEN}");
+        }
+
+        println!(
+            "{}",
+            self.render_multi(multi_game_state).join("\n")
+        );
+    }
+
+    fn render(
+        &self,
+        game_state: &GameState,
+    ) -> Vec<String> {
+        let mut lines = self
+            .grid(game_state, game_state.attempts_left());
+        lines.push(String::new());
+        lines.extend(self.keyboard(game_state.keyboard()));
+        lines.extend(self.legend());
+
+        lines
+    }
+
+    /// A header and a grid per board, the rows of every board on the
+    /// same lines. A solved board leaves its unused rows empty.
+    fn render_multi(
+        &self,
+        multi_game_state: &MultiGameState,
+    ) -> Vec<String> {
+        let width = multi_game_state.word_length() * 4 - 1;
+        let attempts_left =
+            multi_game_state.attempts_left();
+
+        let grids: Vec<Vec<String>> = multi_game_state
+            .boards()
+            .iter()
+            .enumerate()
+            .map(|(i, game_state)| {
+                let mut lines = vec![format!(
+                    "{:<width$}",
+                    format!("Board {}", i + 1)
+                )];
+                lines.extend(self.grid(
+                    game_state,
+                    multi_game_state.guesses()
+                        - game_state.attempts().len()
+                        + attempts_left,
+                ));
+                lines
+            })
+            .collect();
+
+        let mut lines: Vec<String> = (0..grids[0].len())
+            .map(|row| {
+                grids
+                    .iter()
+                    .map(|grid| grid[row].as_str())
+                    .collect::<Vec<_>>()
+                    .join("   ")
+            })
+            .collect();
+        lines.extend(self.legend());
+
+        lines
+    }
+
+    /// The attempts of the game, then as many empty rows as asked.
+    fn grid(
+        &self,
+        game_state: &GameState,
+        empty_rows: usize,
+    ) -> Vec<String> {
+        let mut lines: Vec<String> = game_state
+            .attempts()
+            .iter()
+            .map(|attempt| self.row(attempt))
+            .collect();
+
+        let empty_row =
+            vec![
+                self.tile(" ", LetterState::Unknown);
+                game_state.word_length()
+            ]
+            .join(" ");
+
+        lines.extend(vec![empty_row; empty_rows]);
+
+        lines
+    }
+
+    fn legend(&self) -> Vec<String> {
+        if self.color {
+            return vec![];
+        }
+
+        vec![
+            String::new(),
+            "[X] right spot, (X) wrong spot, -X- not in the word"
+                .to_string(),
+        ]
+    }
+
+    fn row(&self, attempt: &[CharResult]) -> String {
+        attempt
+            .iter()
+            .map(|r| {
+                self.tile(r.letter(), r.result().into())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn tile(
+        &self,
+        letter: &str,
+        state: LetterState,
+    ) -> String {
+        let letter = letter.to_uppercase();
+
+        if self.color {
+            let color = match state {
+                LetterState::Correct => CORRECT,
+                LetterState::Present => PRESENT,
+                LetterState::Absent => ABSENT,
+                LetterState::Unknown => UNKNOWN,
+            };
+
+            return format!("{color} {letter} {RESET}");
+        }
+
+        match state {
+            LetterState::Correct => format!("[{letter}]"),
+            LetterState::Present => format!("({letter})"),
+            LetterState::Absent => format!("-{letter}-"),
+            LetterState::Unknown => format!(" {letter} "),
+        }
+    }
+
+    /// One line per keyboard row, then the letters of the word list
+    /// that are not on the keyboard.
+    fn keyboard(&self, keyboard: &Keyboard) -> Vec<String> {
+        let mut rows: Vec<Vec<String>> = KEYBOARD_ROWS
+            .iter()
+            .map(|row| {
+                row.chars().map(String::from).collect()
+            })
+            .collect();
+
+        let others: Vec<String> = keyboard
+            .letters()
+            .map(|(letter, _)| letter)
+            .filter(|letter| {
+                !KEYBOARD_ROWS
+                    .iter()
+                    .any(|row| row.contains(letter))
+            })
+            .map(String::from)
+            .collect();
+
+        if !others.is_empty() {
+            rows.push(others);
+        }
+
+        rows.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let keys = row
+                    .iter()
+                    .map(|letter| {
+                        self.tile(
+                            letter,
+                            keyboard.state_of(letter),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("");
+
+                format!("{}{}", " ".repeat(i.min(2)), keys)
+            })
+            .collect()
+    }
+}
@@ -1,21 +1,549 @@
+mod board;
+
+use board::Board;
+use chrono::{Duration, NaiveDate};
+use clap::{Parser, Subcommand, ValueEnum};
 use error_stack::ResultExt;
 use lib::{
     app_error::{AppError, AppResult},
+    app_state::AppState,
+    bench::{
+        BenchReport, FirstCandidate, MaxEntropy, Strategy,
+    },
     clock::RealClock,
-    core::{pick_word, with_app_state},
+    core::with_app_state,
+    daily::{DailySchedule, DEFAULT_SEED},
+    game_config::{
+        GameConfig, GameMode, DEFAULT_MAX_ATTEMPTS,
+        DEFAULT_MAX_WORD_LENGTH, DEFAULT_MIN_WORD_LENGTH,
+    },
+    game_snapshot::GameSnapshot,
+    game_state::{
+        GameState, ADVERSARIAL_NOTICE, HARD_MODE_NOTICE,
+    },
+    hint::DEFAULT_HINT_BUDGET,
+    multi_game_state::default_max_attempts,
+    player_stats::PlayerStats,
     session_state::SessionState,
+    share_grid::{share_grid, ShareTheme},
+    solver::Solver,
+    word_list::{
+        check_env, install_from, WordList, WordListSource,
+    },
+    word_list_report::WordListReport,
+    word_picker::{
+        FixedPicker, RandomPicker, SeededPicker, WordPicker,
+    },
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
 };
-use std::io;
 
-fn main() -> AppResult<()> {
-    with_app_state(|state| {
-        let game_state =
-            state.new_game_with(pick_word, &RealClock)?;
+const HINT_COMMAND: &str = "?";
+
+/// Play Golem Wordle in the terminal.
+#[derive(Parser)]
+struct Cli {
+    /// Number of guesses allowed per game [default: 6, or 5 more
+    /// than the boards]
+    #[arg(long)]
+    max_attempts: Option<usize>,
+
+    /// Shortest word that may be picked.
+    #[arg(long, default_value_t = DEFAULT_MIN_WORD_LENGTH)]
+    min_word_length: usize,
+
+    /// Longest word that may be picked.
+    #[arg(long, default_value_t = DEFAULT_MAX_WORD_LENGTH)]
+    max_word_length: usize,
+
+    /// Revealed hints must be used in every later guess.
+    #[arg(long)]
+    hard: bool,
+
+    /// The word keeps changing to dodge your guesses, until only one
+    /// word fits them.
+    #[arg(long, conflicts_with_all = ["daily", "word"])]
+    adversarial: bool,
+
+    /// Guess 2 or 4 words at once, each guess played on every board
+    /// not solved yet.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        conflicts_with_all = ["adversarial", "save", "daily", "word", "assist"]
+    )]
+    boards: Option<usize>,
+
+    /// Number of hints allowed per game.
+    #[arg(long, default_value_t = DEFAULT_HINT_BUDGET)]
+    hints: usize,
+
+    /// Seconds allowed for the whole game.
+    #[arg(long, value_name = "SECONDS")]
+    time_limit: Option<u32>,
+
+    /// Seconds allowed for each guess.
+    #[arg(long, value_name = "SECONDS")]
+    guess_time_limit: Option<u32>,
+
+    /// After every guess, show how many words still fit and the most
+    /// informative next guesses.
+    #[arg(long)]
+    assist: bool,
+
+    /// Save the game to this file after every guess, and resume it
+    /// from there on the next start.
+    #[arg(long, value_name = "PATH")]
+    save: Option<PathBuf>,
+
+    /// Play today's puzzle: the same word for everyone on the same day.
+    #[arg(long, conflicts_with_all = ["seed", "word"])]
+    daily: bool,
+
+    /// Day of daily puzzle #1.
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        requires = "daily"
+    )]
+    daily_epoch: Option<NaiveDate>,
+
+    /// Seed mixed into the daily word selection.
+    #[arg(long, requires = "daily")]
+    daily_seed: Option<u64>,
+
+    /// Pick words from this seed, so that a run can be replayed.
+    #[arg(long, conflicts_with = "word")]
+    seed: Option<u64>,
+
+    /// Play this word from the word list.
+    #[arg(long)]
+    word: Option<String>,
+
+    /// Load the word list from this file instead of the built-in one.
+    #[arg(long, value_name = "PATH")]
+    words: Option<PathBuf>,
+
+    /// Keep your statistics in this file across games.
+    #[arg(long, value_name = "PATH")]
+    stats_file: Option<PathBuf>,
+
+    /// Show your statistics and exit.
+    #[arg(long, requires = "stats_file")]
+    stats: bool,
+
+    /// Colors of the result grid printed at the end of a game.
+    #[arg(long, value_enum, default_value_t = Theme::Dark)]
+    share_theme: Theme,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Theme {
+    Light,
+    Dark,
+    /// Orange and blue instead of green and yellow.
+    HighContrast,
+}
+
+impl From<Theme> for ShareTheme {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Light => ShareTheme::Light,
+            Theme::Dark => ShareTheme::Dark,
+            Theme::HighContrast => ShareTheme::HighContrast,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check a word list and print what is wrong with it.
+    ValidateWords {
+        /// Word list to check, the built-in one by default.
+        path: Option<PathBuf>,
+
+        /// Fail on the first problem found.
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Play every word of a word list with a solver, and fail when a
+    /// word is not solved within the allowed attempts.
+    Bench {
+        /// Word list to play, the built-in one by default.
+        path: Option<PathBuf>,
+
+        /// How the solver picks its next guess.
+        #[arg(long, value_enum, default_value_t = BenchStrategy::MaxEntropy)]
+        strategy: BenchStrategy,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BenchStrategy {
+    /// The guess expected to tell the most about the word.
+    MaxEntropy,
+    /// The first word of the list that could still be the word.
+    FirstCandidate,
+}
+
+impl BenchStrategy {
+    fn strategy(self) -> Box<dyn Strategy> {
+        match self {
+            BenchStrategy::MaxEntropy => {
+                Box::new(MaxEntropy)
+            }
+            BenchStrategy::FirstCandidate => {
+                Box::new(FirstCandidate)
+            }
+        }
+    }
+}
+
+impl Cli {
+    fn max_attempts(&self) -> usize {
+        self.max_attempts.unwrap_or(match self.boards {
+            Some(boards) => default_max_attempts(boards),
+            None => DEFAULT_MAX_ATTEMPTS,
+        })
+    }
+
+    fn daily_schedule(&self) -> Option<DailySchedule> {
+        if !self.daily {
+            return None;
+        }
+
+        let default = DailySchedule::default();
+
+        Some(DailySchedule::new(
+            self.daily_epoch.unwrap_or(default.epoch()),
+            self.daily_seed.unwrap_or(DEFAULT_SEED),
+        ))
+    }
+
+    fn word_picker(
+        &self,
+    ) -> AppResult<Box<dyn WordPicker>> {
+        if let Some(word) = &self.word {
+            return Ok(Box::new(
+                FixedPicker::from_word_list(word)?,
+            ));
+        }
+
+        Ok(match self.seed {
+            Some(seed) => Box::new(SeededPicker::new(seed)),
+            None => Box::new(RandomPicker),
+        })
+    }
+}
+
+/// Answers the id of the saved game, unless it is already over.
+fn resume_game(
+    state: &mut AppState<'static>,
+    path: &Path,
+) -> AppResult<Option<u64>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let snapshot = GameSnapshot::load(path)?;
+    let (id, game_state) =
+        state.restore_game_from(&snapshot, &RealClock)?;
+
+    if game_state.is_finished() {
+        state.abandon_game(id)?;
+        return Ok(None);
+    }
+
+    Ok(Some(id))
+}
+
+fn save_game(
+    game_state: &GameState,
+    path: &Path,
+) -> AppResult<()> {
+    GameSnapshot::of(game_state)?.save(path)
+}
+
+fn discard_saved_game(path: &Path) -> AppResult<()> {
+    if path.exists() {
+        fs::remove_file(path).change_context(
+            AppError::FileWrite(path.display().to_string()),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn word_list_source(path: Option<&Path>) -> WordListSource {
+    match path {
+        Some(path) => {
+            WordListSource::File(path.to_path_buf())
+        }
+        None => WordListSource::BuiltIn,
+    }
+}
+
+fn validate_words(
+    path: Option<&Path>,
+    strict: bool,
+) -> AppResult<()> {
+    let source = word_list_source(path);
+
+    let report = WordListReport::of(&source.bytes()?)?;
+
+    println!("{}", report.describe().join("\n"));
+
+    if strict {
+        report.strict()?;
+    }
+
+    Ok(())
+}
+
+fn bench(
+    path: Option<&Path>,
+    strategy: BenchStrategy,
+    max_attempts: usize,
+) -> AppResult<()> {
+    let words = WordList::load(&word_list_source(path))?;
+
+    let report = BenchReport::run(
+        words.words(),
+        strategy.strategy().as_ref(),
+        max_attempts,
+    );
+
+    println!("{}", report.describe().join("\n"));
+
+    report.strict()
+}
+
+fn play_multi_game(
+    state: &mut AppState<'static>,
+    cli: &Cli,
+    board: &Board,
+    boards: usize,
+    config: GameConfig,
+) -> AppResult<()> {
+    let (id, multi_game_state) = state
+        .new_multi_game_with(
+            cli.word_picker()?.as_mut(),
+            boards,
+            config,
+            &RealClock,
+        )?;
+
+    board.draw_multi(multi_game_state);
+
+    println!(
+        "Welcome to Golem Wordle! Please describe Golem in {} words of {} letters at once.",
+        boards,
+        multi_game_state.word_length()
+    );
+    println!(
+        "You have {} attempts to solve every board.",
+        multi_game_state.attempts_left()
+    );
+
+    if multi_game_state.config().hard_mode() {
+        println!("{HARD_MODE_NOTICE}");
+    }
+
+    loop {
+        println!("\nPlease enter your guess: ");
+
+        let mut user_input = String::new();
+        io::stdin()
+            .read_line(&mut user_input)
+            .change_context(AppError::StdIoRead)?;
+
+        let session_state = match multi_game_state
+            .guess(user_input.trim())
+        {
+            Ok(s) => s,
+            Err(e) => match e.current_context() {
+                AppError::InvalidGuessLength(_)
+                | AppError::UnknownWord(_)
+                | AppError::HardModeViolation(_) => {
+                    eprintln!("*** ERROR: {}", e);
+                    continue;
+                }
+                // we propagate other kind of errors
+                _ => return Err(e),
+            },
+        };
+
+        board.draw_multi(multi_game_state);
+
+        if session_state.is_over() {
+            println!(
+                "{}",
+                session_state.messages().join("\n")
+            );
+            state.end_multi_game(id);
+            break;
+        }
 
         println!(
-            "Welcome to Golem Wordle! Please describe Golem in a {}-letter word.",
-            game_state.word_length()
+            "You have solved {} of {} boards, with {} attempts left.",
+            multi_game_state.solved_count(),
+            boards,
+            multi_game_state.attempts_left()
         );
+    }
+
+    Ok(())
+}
+
+fn main() -> AppResult<()> {
+    let cli = Cli::parse();
+
+    match &cli.command {
+        Some(Command::ValidateWords { path, strict }) => {
+            return validate_words(
+                path.as_deref(),
+                *strict,
+            );
+        }
+        Some(Command::Bench { path, strategy }) => {
+            return bench(
+                path.as_deref(),
+                *strategy,
+                cli.max_attempts(),
+            );
+        }
+        None => {}
+    }
+
+    let config = GameConfig::new(
+        cli.max_attempts(),
+        cli.min_word_length,
+        cli.max_word_length,
+        cli.hard,
+    )?
+    .with_hint_budget(cli.hints)
+    .with_mode(if cli.adversarial {
+        GameMode::Adversarial
+    } else {
+        GameMode::Classic
+    })
+    .with_time_limits(
+        cli.time_limit.map(|s| Duration::seconds(s.into())),
+        cli.guess_time_limit
+            .map(|s| Duration::seconds(s.into())),
+    )?;
+
+    // the words named by the environment are loaded on first use, so
+    // a broken list is reported now rather than stopping a game
+    check_env()?;
+
+    if let Some(path) = &cli.words {
+        install_from(&WordListSource::File(path.clone()))?;
+    }
+
+    let stats = match &cli.stats_file {
+        Some(path) => PlayerStats::load(path)?,
+        None => PlayerStats::default(),
+    };
+
+    if cli.stats {
+        println!("{}", stats.describe().join("\n"));
+        return Ok(());
+    }
+
+    with_app_state(|state| {
+        state.restore_stats(stats);
+
+        let board = Board::detect();
+
+        if let Some(boards) = cli.boards {
+            return play_multi_game(
+                state, &cli, &board, boards, config,
+            );
+        }
+
+        let resumed = match &cli.save {
+            Some(path) => resume_game(state, path)?,
+            None => None,
+        };
+
+        let (id, game_state) = if let Some(id) = resumed {
+            (id, state.game_as_mut(id)?)
+        } else {
+            match cli.daily_schedule() {
+                Some(schedule) => state
+                    .new_daily_game_with(
+                        &schedule, config, &RealClock,
+                    )?,
+                None => state.new_game_with(
+                    cli.word_picker()?.as_mut(),
+                    config,
+                    &RealClock,
+                )?,
+            }
+        };
+
+        board.draw(game_state);
+
+        if resumed.is_some() {
+            println!(
+                "Welcome back to Golem Wordle! Resuming your {}-letter word.",
+                game_state.word_length()
+            );
+            println!(
+                "You have {} attempts left.",
+                game_state.attempts_left()
+            );
+        } else {
+            if let Some(number) = game_state.puzzle_number()
+            {
+                println!("Golem Wordle #{number}");
+            }
+
+            println!(
+                "Welcome to Golem Wordle! Please describe Golem in a {}-letter word.",
+                game_state.word_length()
+            );
+        }
+
+        if game_state.config().hard_mode() {
+            println!("{HARD_MODE_NOTICE}");
+        }
+
+        if game_state.config().is_adversarial() {
+            println!("{ADVERSARIAL_NOTICE}");
+        }
+
+        if let Some(time) = game_state.describe_time() {
+            println!("{time}");
+        }
+
+        if game_state.hints_left() > 0
+            && !game_state.config().is_adversarial()
+        {
+            println!(
+                "Enter {HINT_COMMAND} instead of a guess to get a hint ({} left).",
+                game_state.hints_left()
+            );
+        }
+
+        let solver = cli
+            .assist
+            .then(|| Solver::new(game_state.word_length()));
+
+        if let Some(solver) = &solver {
+            println!(
+                "{}",
+                solver
+                    .describe(game_state.attempts())
+                    .join("\n")
+            );
+        }
 
         loop {
             println!("\nPlease enter your guess: ");
@@ -25,6 +553,27 @@ fn main() -> AppResult<()> {
                 .read_line(&mut user_input)
                 .change_context(AppError::StdIoRead)?;
 
+            if user_input.trim() == HINT_COMMAND {
+                match game_state.hint() {
+                    Ok(hint) => {
+                        board.draw(game_state);
+                        println!("{hint}");
+                        println!(
+                            "You have {} hints left.",
+                            game_state.hints_left()
+                        );
+                    }
+                    Err(e) => match e.current_context() {
+                        AppError::NoHintsLeft(_)
+                        | AppError::NoHintAvailable => {
+                            eprintln!("*** ERROR: {}", e);
+                        }
+                        _ => return Err(e),
+                    },
+                }
+                continue;
+            }
+
             let session_state = SessionState::determined_by(
                 user_input.trim(),
                 game_state,
@@ -32,7 +581,9 @@ fn main() -> AppResult<()> {
             let session_state = match session_state {
                 Ok(s) => s,
                 Err(e) => match e.current_context() {
-                    AppError::InvalidGuessLength(_) => {
+                    AppError::InvalidGuessLength(_)
+                    | AppError::UnknownWord(_)
+                    | AppError::HardModeViolation(_) => {
                         eprintln!("*** ERROR: {}", e);
                         continue;
                     }
@@ -41,17 +592,59 @@ fn main() -> AppResult<()> {
                 },
             };
 
-            println!();
+            board.draw(game_state);
 
             match session_state {
                 SessionState::Won(msg)
-                | SessionState::Lost(msg) => {
-                    state.set_empty();
+                | SessionState::Lost(msg)
+                | SessionState::TimedOut(msg) => {
+                    state.end_game(id);
+                    if let Some(path) = &cli.save {
+                        discard_saved_game(path)?;
+                    }
+                    if let Some(path) = &cli.stats_file {
+                        state.stats().save(path)?;
+                    }
                     println!("{msg}");
+                    let record = state.history().last()?;
+                    println!(
+                        "\n{}",
+                        share_grid(
+                            record,
+                            cli.share_theme.into()
+                        )
+                        .join("\n")
+                    );
                     break;
                 }
-                SessionState::InProgress { summaries } => {
-                    println!("{}", summaries.join("\n"));
+                SessionState::InProgress { .. } => {
+                    if let Some(path) = &cli.save {
+                        save_game(game_state, path)?;
+                    }
+                    println!(
+                        "You have {} attempts left.",
+                        game_state.attempts_left()
+                    );
+                    if let Some(candidates) =
+                        game_state.describe_candidates()
+                    {
+                        println!("{candidates}");
+                    }
+                    if let Some(time) =
+                        game_state.describe_time()
+                    {
+                        println!("{time}");
+                    }
+                    if let Some(solver) = &solver {
+                        println!(
+                            "{}",
+                            solver
+                                .describe(
+                                    game_state.attempts()
+                                )
+                                .join("\n")
+                        );
+                    }
                 }
             }
         }
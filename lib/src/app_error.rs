@@ -1,4 +1,7 @@
-use crate::core::WORDS_FILE_PATH;
+use crate::{
+    core::WORDS_FILE_PATH,
+    hard_mode::HardModeRule,
+};
 use error_stack::Context;
 use kinded::Kinded;
 use sealed::sealed;
@@ -32,6 +35,35 @@ pub enum AppError {
     StdIoRead,
     InvalidCharset,
     InvalidGuessLength(usize),
+    UnknownWord(String),
+    InvalidGameConfig(String),
+    HardModeViolation(HardModeRule),
+    NoGameInProgress,
+    InvalidSnapshot(String),
+    SnapshotEncode,
+    SnapshotDecode,
+    FileRead(String),
+    FileWrite(String),
+    DailyPuzzleNotStarted(String),
+    MixedCaseWord(String),
+    InvalidWord(String),
+    DuplicateWord(String),
+    StatsEncode,
+    StatsDecode,
+    GameNotFound(u64),
+    NoFinishedGame,
+    NoHintsLeft(usize),
+    NoHintAvailable,
+    UnsolvedWord(String),
+    GameNotInProgress(u64),
+    TooManyGames(usize),
+    InvalidGameLimits(String),
+    NoRaceInProgress,
+    RaceOver,
+    InvalidPlayerName,
+    PlayerAlreadyInRace(String),
+    PlayerNotInRace(String),
+    ChallengeNotFound(String),
 }
 
 impl Display for AppError {
@@ -66,6 +98,226 @@ impl Display for AppError {
                     expected_len
                 )
             }
+            E::UnknownWord(guess) => {
+                write!(
+                    f,
+                    "[{:?}] '{}' is not in the word list.",
+                    AppErrorKind::UnknownWord,
+                    guess
+                )
+            }
+            E::InvalidGameConfig(
+                reason,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] Invalid game config: {}.",
+                    AppErrorKind::InvalidGameConfig,
+                    reason
+                )
+            }
+            E::HardModeViolation(
+                rule,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] Hard mode: {}.",
+                    AppErrorKind::HardModeViolation,
+                    rule
+                )
+            }
+            E::NoGameInProgress => {
+                write!(
+                    f,
+                    "[{:?}] Currently no game in progress.",
+                    E::NoGameInProgress
+                )
+            }
+            E::InvalidSnapshot(
+                reason,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] Invalid game snapshot: {}.",
+                    AppErrorKind::InvalidSnapshot,
+                    reason
+                )
+            }
+            E::SnapshotEncode => {
+                write!(f, "[{:?}] Failed to encode the game snapshot.", E::SnapshotEncode)
+            }
+            E::SnapshotDecode => {
+                write!(f, "[{:?}] Failed to decode the game snapshot.", E::SnapshotDecode)
+            }
+            E::StatsEncode => {
+                write!(f, "[{:?}] Failed to encode the player stats.", E::StatsEncode)
+            }
+            E::StatsDecode => {
+                write!(f, "[{:?}] Failed to decode the player stats.", E::StatsDecode)
+            }
+            E::GameNotFound(id) => {
+                write!(
+                    f,
+                    "[{:?}] There is no game #{} in the history.",
+                    AppErrorKind::GameNotFound,
+                    id
+                )
+            }
+            E::NoFinishedGame => {
+                write!(
+                    f,
+                    "[{:?}] No game has been finished yet.",
+                    E::NoFinishedGame
+                )
+            }
+            E::NoHintsLeft(budget) => {
+                write!(
+                    f,
+                    "[{:?}] All {} hints of this game have been used.",
+                    AppErrorKind::NoHintsLeft,
+                    budget
+                )
+            }
+            E::NoHintAvailable => {
+                write!(
+                    f,
+                    "[{:?}] There is nothing left to hint at.",
+                    E::NoHintAvailable
+                )
+            }
+            E::UnsolvedWord(word) => {
+                write!(
+                    f,
+                    "[{:?}] The word '{}' could not be solved within the allowed attempts.",
+                    AppErrorKind::UnsolvedWord,
+                    word
+                )
+            }
+            E::GameNotInProgress(id) => {
+                write!(
+                    f,
+                    "[{:?}] There is no game #{} in progress.",
+                    AppErrorKind::GameNotInProgress,
+                    id
+                )
+            }
+            E::TooManyGames(max_games) => {
+                write!(
+                    f,
+                    "[{:?}] {} games are already in progress. Finish or abandon one of them first.",
+                    AppErrorKind::TooManyGames,
+                    max_games
+                )
+            }
+            E::InvalidGameLimits(
+                reason,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] Invalid game limits: {}.",
+                    AppErrorKind::InvalidGameLimits,
+                    reason
+                )
+            }
+            E::NoRaceInProgress => {
+                write!(
+                    f,
+                    "[{:?}] Currently no race in progress.",
+                    E::NoRaceInProgress
+                )
+            }
+            E::RaceOver => {
+                write!(
+                    f,
+                    "[{:?}] This race is over.",
+                    E::RaceOver
+                )
+            }
+            E::InvalidPlayerName => {
+                write!(
+                    f,
+                    "[{:?}] A player name cannot be blank.",
+                    E::InvalidPlayerName
+                )
+            }
+            E::PlayerAlreadyInRace(
+                name,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] '{}' has already joined this race.",
+                    AppErrorKind::PlayerAlreadyInRace,
+                    name
+                )
+            }
+            E::PlayerNotInRace(name) => {
+                write!(
+                    f,
+                    "[{:?}] '{}' has not joined this race.",
+                    AppErrorKind::PlayerNotInRace,
+                    name
+                )
+            }
+            E::ChallengeNotFound(
+                code,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] There is no challenge with the code '{}'.",
+                    AppErrorKind::ChallengeNotFound,
+                    code
+                )
+            }
+            E::FileRead(path) => {
+                write!(
+                    f,
+                    "[{:?}] Failed to read file '{}'.",
+                    AppErrorKind::FileRead,
+                    path
+                )
+            }
+            E::FileWrite(path) => {
+                write!(
+                    f,
+                    "[{:?}] Failed to write file '{}'.",
+                    AppErrorKind::FileWrite,
+                    path
+                )
+            }
+            E::DailyPuzzleNotStarted(
+                epoch,
+            ) => {
+                write!(
+                    f,
+                    "[{:?}] The daily puzzle starts on {}.",
+                    AppErrorKind::DailyPuzzleNotStarted,
+                    epoch
+                )
+            }
+            E::MixedCaseWord(word) => {
+                write!(
+                    f,
+                    "[{:?}] The word '{}' is not all lowercase.",
+                    AppErrorKind::MixedCaseWord,
+                    word
+                )
+            }
+            E::InvalidWord(word) => {
+                write!(
+                    f,
+                    "[{:?}] The word '{}' contains characters other than letters.",
+                    AppErrorKind::InvalidWord,
+                    word
+                )
+            }
+            E::DuplicateWord(word) => {
+                write!(
+                    f,
+                    "[{:?}] The word '{}' appears more than once.",
+                    AppErrorKind::DuplicateWord,
+                    word
+                )
+            }
         }
     }
 }
@@ -1,80 +1,874 @@
 use crate::{
-    app_error::AppResult, clock::Clock,
+    app_error::{AppError, AppResult},
+    challenge::{
+        Challenge, Challenges,
+    },
+    clock::Clock,
+    daily::DailySchedule,
+    game_config::GameConfig,
+    game_history::{
+        GameHistory, ReplayStep,
+        DEFAULT_HISTORY_CAPACITY,
+    },
+    game_limits::GameLimits,
+    game_snapshot::GameSnapshot,
     game_state::GameState,
+    multi_game_state::MultiGameState,
+    player_stats::PlayerStats,
+    race::Race,
+    word_picker::{
+        FixedPicker, WordPicker,
+    },
 };
+use error_stack::bail;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
 
-pub struct AppState<'a>(
-    Option<GameState<'a>>,
-);
+static LIMITS_FROM_ENV: Lazy<
+    GameLimits,
+> = Lazy::new(GameLimits::from_env);
+
+pub struct AppState<'a> {
+    games: BTreeMap<u64, GameState<'a>>,
+    multi_games: BTreeMap<
+        u64,
+        MultiGameState<'a>,
+    >,
+    next_game_id: u64,
+    limits: Option<GameLimits>,
+    race: Option<Race<'a>>,
+    challenges: Challenges,
+    stats: PlayerStats,
+    history: GameHistory,
+}
 impl<'a> AppState<'a> {
     pub(crate) const fn empty() -> Self
     {
-        Self(None)
+        Self {
+            games: BTreeMap::new(),
+            multi_games: BTreeMap::new(),
+            next_game_id: 1,
+            limits: None,
+            race: None,
+            challenges: Challenges::new(),
+            stats: PlayerStats::new(),
+            history: GameHistory::new(
+                DEFAULT_HISTORY_CAPACITY,
+            ),
+        }
     }
 
-    pub fn game_state(
+    pub fn game(
         &self,
-    ) -> Option<&GameState<'a>> {
-        self.0.as_ref()
+        id: u64,
+    ) -> AppResult<&GameState<'a>> {
+        match self.games.get(&id) {
+            Some(game_state) => {
+                Ok(game_state)
+            }
+            None => bail!(
+                AppError::GameNotInProgress(
+                    id
+                )
+            ),
+        }
     }
 
-    pub fn game_state_as_mut(
+    pub fn game_as_mut(
         &mut self,
-    ) -> Option<&mut GameState<'a>>
+        id: u64,
+    ) -> AppResult<&mut GameState<'a>>
     {
-        self.0.as_mut()
+        match self.games.get_mut(&id) {
+            Some(game_state) => {
+                Ok(game_state)
+            }
+            None => bail!(
+                AppError::GameNotInProgress(
+                    id
+                )
+            ),
+        }
     }
 
-    pub fn set_empty(&mut self) {
-        self.0 = None;
+    /// The games in progress, oldest first.
+    pub fn games(
+        &self,
+    ) -> impl Iterator<
+        Item = (u64, &GameState<'a>),
+    > {
+        self.games
+            .iter()
+            .map(|(id, g)| (*id, g))
     }
 
-    pub fn new_game_with(
+    /// The limits set with `set_limits`, or else the ones read from
+    /// the environment.
+    pub fn limits(
+        &self,
+    ) -> &GameLimits {
+        self.limits
+            .as_ref()
+            .unwrap_or(&LIMITS_FROM_ENV)
+    }
+
+    pub fn set_limits(
         &mut self,
-        f: impl FnOnce()
-            -> AppResult<&'a str>,
+        limits: GameLimits,
+    ) {
+        self.limits = Some(limits);
+    }
+
+    /// Drops the games nobody has played for longer than the limits
+    /// allow, answering their ids. They are not counted in the stats.
+    /// A race goes the same way once none of its players is active.
+    pub fn drop_stale_games(
+        &mut self,
+    ) -> Vec<u64> {
+        let stale_after =
+            self.limits().stale_after();
+        let stale: Vec<_> = self
+            .games
+            .iter()
+            .filter(|(_, g)| {
+                g.last_update_older_than(
+                    &stale_after,
+                )
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in &stale {
+            self.games.remove(id);
+            self.challenges
+                .untrack(*id);
+        }
+
+        let stale_multi: Vec<_> = self
+            .multi_games
+            .iter()
+            .filter(|(_, g)| {
+                g.last_update_older_than(
+                    &stale_after,
+                )
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in &stale_multi {
+            self.multi_games.remove(id);
+        }
+
+        let stale_race = self
+            .race
+            .as_ref()
+            .filter(|r| {
+                r.last_update_older_than(
+                    &stale_after,
+                )
+            })
+            .map(Race::id);
+
+        if stale_race.is_some() {
+            self.race = None;
+        }
+
+        stale
+            .into_iter()
+            .chain(stale_multi)
+            .chain(stale_race)
+            .collect()
+    }
+
+    /// Counts the game in the stats and keeps it in the history if it
+    /// is finished, and removes it from the games in progress.
+    pub fn end_game(
+        &mut self,
+        id: u64,
+    ) {
+        if let Some(game_state) =
+            self.games.remove(&id)
+        {
+            self.stats
+                .record(&game_state);
+            self.challenges.record(
+                id,
+                &game_state,
+            );
+            self.history.record(
+                id,
+                &game_state,
+            );
+        }
+    }
+
+    /// Gives up on a game in progress, which counts as a loss in the
+    /// stats. Multi-board games are not counted.
+    pub fn abandon_game(
+        &mut self,
+        id: u64,
+    ) -> AppResult<()> {
+        if self
+            .multi_games
+            .remove(&id)
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        match self.games.remove(&id) {
+            Some(game_state) => {
+                self.stats
+                    .record_abandoned(
+                        &game_state,
+                    );
+                self.challenges
+                    .untrack(id);
+
+                Ok(())
+            }
+            None => bail!(
+                AppError::GameNotInProgress(
+                    id
+                )
+            ),
+        }
+    }
+
+    pub fn multi_game(
+        &self,
+        id: u64,
+    ) -> AppResult<&MultiGameState<'a>>
+    {
+        match self.multi_games.get(&id) {
+            Some(game) => Ok(game),
+            None => bail!(
+                AppError::GameNotInProgress(
+                    id
+                )
+            ),
+        }
+    }
+
+    pub fn multi_game_as_mut(
+        &mut self,
+        id: u64,
+    ) -> AppResult<
+        &mut MultiGameState<'a>,
+    > {
+        match self
+            .multi_games
+            .get_mut(&id)
+        {
+            Some(game) => Ok(game),
+            None => bail!(
+                AppError::GameNotInProgress(
+                    id
+                )
+            ),
+        }
+    }
+
+    /// Starts a game of several boards. It counts towards the games in
+    /// progress, but is kept out of the stats and the history.
+    pub fn new_multi_game_with(
+        &mut self,
+        picker: &mut dyn WordPicker,
+        board_count: usize,
+        config: GameConfig,
         clock: &'a impl Clock,
-    ) -> AppResult<&mut GameState<'a>>
+    ) -> AppResult<(
+        u64,
+        &mut MultiGameState<'a>,
+    )> {
+        self.make_room()?;
+
+        let game =
+            MultiGameState::new_with(
+                picker,
+                board_count,
+                config,
+                clock,
+            )?;
+
+        let id = self.next_free_id()?;
+
+        Ok((
+            id,
+            self.multi_games
+                .entry(id)
+                .or_insert(game),
+        ))
+    }
+
+    /// Removes a multi-board game from the games in progress.
+    pub fn end_multi_game(
+        &mut self,
+        id: u64,
+    ) {
+        self.multi_games.remove(&id);
+    }
+
+    pub fn race(
+        &self,
+    ) -> AppResult<&Race<'a>> {
+        match &self.race {
+            Some(race) => Ok(race),
+            None => bail!(
+                AppError::NoRaceInProgress
+            ),
+        }
+    }
+
+    pub fn race_as_mut(
+        &mut self,
+    ) -> AppResult<&mut Race<'a>> {
+        match &mut self.race {
+            Some(race) => Ok(race),
+            None => bail!(
+                AppError::NoRaceInProgress
+            ),
+        }
+    }
+
+    /// Joins the race in progress, or starts a new one on a word from
+    /// the picker when there is none or the last one is over. Races
+    /// share their ids with games, and are not counted in the stats.
+    pub fn join_race_with(
+        &mut self,
+        name: &str,
+        picker: &mut dyn WordPicker,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<&mut Race<'a>> {
+        self.drop_stale_games();
+
+        let is_open = self
+            .race
+            .as_ref()
+            .is_some_and(|r| {
+                !r.is_over()
+            });
+
+        if !is_open {
+            self.make_room()?;
+
+            let word =
+                picker.pick(&config)?;
+
+            self.race = Some(Race::of(
+                self.next_free_id()?,
+                word,
+                config,
+                clock,
+            ));
+        }
+
+        let race =
+            self.race_as_mut()?;
+        race.join(name, clock)?;
+
+        Ok(race)
+    }
+
+    /// Creates a challenge for a word of the word list, for others to
+    /// play by its code.
+    pub fn create_challenge(
+        &mut self,
+        word: &str,
+        clock: &impl Clock,
+    ) -> AppResult<&Challenge> {
+        self.drop_stale_challenges(
+            clock,
+        );
+
+        self.challenges
+            .create(word, clock)
+    }
+
+    pub fn challenge(
+        &mut self,
+        code: &str,
+        clock: &impl Clock,
+    ) -> AppResult<&Challenge> {
+        self.drop_stale_challenges(
+            clock,
+        );
+
+        self.challenges.get(code)
+    }
+
+    /// Drops the challenges left alone for as long as a stale game,
+    /// once their own stale games are dropped.
+    fn drop_stale_challenges(
+        &mut self,
+        clock: &impl Clock,
+    ) {
+        self.drop_stale_games();
+
+        let stale_after =
+            self.limits().stale_after();
+
+        self.challenges.drop_stale(
+            clock.now(),
+            &stale_after,
+        );
+    }
+
+    /// Starts a game on the word of the challenge. Its result is added
+    /// to the challenge once it is over.
+    pub fn new_challenge_game(
+        &mut self,
+        code: &str,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<(
+        u64,
+        &mut GameState<'a>,
+    )> {
+        self.drop_stale_challenges(
+            clock,
+        );
+
+        let challenge = self
+            .challenges
+            .get(code)?;
+        let code = challenge
+            .code()
+            .to_string();
+        let mut picker =
+            FixedPicker::new(
+                challenge.word(),
+            );
+
+        let (id, _) = self
+            .new_game_with(
+                &mut picker,
+                config,
+                clock,
+            )?;
+        self.challenges
+            .track(id, &code);
+
+        Ok((id, self.game_as_mut(id)?))
+    }
+
+    pub fn history(
+        &self,
+    ) -> &GameHistory {
+        &self.history
+    }
+
+    pub fn replay(
+        &self,
+        game_id: u64,
+    ) -> AppResult<Vec<ReplayStep>>
     {
-        let word = f()?;
+        self.history.replay(game_id)
+    }
 
-        self.set_empty();
+    pub fn stats(
+        &self,
+    ) -> &PlayerStats {
+        &self.stats
+    }
 
-        let result = self
-            .0
-            .get_or_insert_with(|| {
-                GameState::of(
-                    word, clock,
-                )
+    pub fn restore_stats(
+        &mut self,
+        stats: PlayerStats,
+    ) {
+        self.stats = stats;
+    }
+
+    /// Fails unless there is room for one more game, once the stale
+    /// ones are dropped. Checked before a word is picked, so that a
+    /// full worker leaves the picker as it was.
+    fn make_room(
+        &mut self,
+    ) -> AppResult<()> {
+        self.drop_stale_games();
+
+        let max_games =
+            self.limits().max_games();
+
+        let open_race = self
+            .race
+            .as_ref()
+            .is_some_and(|r| {
+                !r.is_over()
             });
 
-        Ok(result)
+        if self.games.len()
+            + self.multi_games.len()
+            + usize::from(open_race)
+            >= max_games
+        {
+            bail!(
+                AppError::TooManyGames(
+                    max_games
+                )
+            )
+        }
+
+        Ok(())
+    }
+
+    /// Answers the id for a new game, if there is room for one.
+    fn next_free_id(
+        &mut self,
+    ) -> AppResult<u64> {
+        self.make_room()?;
+
+        let id = self.next_game_id;
+        self.next_game_id += 1;
+
+        Ok(id)
+    }
+
+    fn add_game(
+        &mut self,
+        game_state: GameState<'a>,
+    ) -> AppResult<(
+        u64,
+        &mut GameState<'a>,
+    )> {
+        let id = self.next_free_id()?;
+
+        Ok((
+            id,
+            self.games
+                .entry(id)
+                .or_insert(game_state),
+        ))
+    }
+
+    pub fn new_game_with(
+        &mut self,
+        picker: &mut dyn WordPicker,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<(
+        u64,
+        &mut GameState<'a>,
+    )> {
+        self.make_room()?;
+
+        let word =
+            picker.pick(&config)?;
+
+        self.add_game(GameState::of(
+            word, config, clock,
+        ))
+    }
+
+    pub fn new_daily_game_with(
+        &mut self,
+        schedule: &DailySchedule,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<(
+        u64,
+        &mut GameState<'a>,
+    )> {
+        let puzzle = schedule
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )?;
+
+        let game_state = GameState::of(
+            puzzle.word(),
+            config,
+            clock,
+        )
+        .with_puzzle_number(
+            puzzle.number(),
+        );
+
+        self.add_game(game_state)
+    }
+
+    /// Adds the game of the snapshot as a new game in progress.
+    pub fn restore_game_from(
+        &mut self,
+        snapshot: &GameSnapshot,
+        clock: &'a impl Clock,
+    ) -> AppResult<(
+        u64,
+        &mut GameState<'a>,
+    )> {
+        let game_state =
+            snapshot.restore(clock)?;
+
+        self.add_game(game_state)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clock::RealClock;
+    use crate::{
+        assert_app_error,
+        clock::{
+            tests::TestClock, RealClock,
+        },
+        core::word_by_id,
+        word_picker::{
+            FixedPicker, SeededPicker,
+            SequentialPicker,
+        },
+    };
+    use chrono::Duration;
     use pretty_assertions::assert_eq;
 
     const DUMMY: &str = "dummy";
 
-    // fn set_empty
+    fn new_dummy_game<'a>(
+        app_state: &mut AppState<'a>,
+        clock: &'a impl Clock,
+    ) -> AppResult<u64> {
+        app_state
+            .new_game_with(
+                &mut FixedPicker::new(
+                    DUMMY,
+                ),
+                GameConfig::default(),
+                clock,
+            )
+            .map(|(id, _)| id)
+    }
+
+    // fn abandon_game
     #[test]
-    fn set_empty_should_set_the_inner_element_to_none(
+    fn abandon_game_should_remove_the_game_and_count_it_as_a_loss(
     ) {
-        let dummy_game_state =
-            GameState::of(
-                DUMMY, &RealClock,
+        let mut app_state =
+            AppState::empty();
+        let id = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        )
+        .unwrap();
+
+        app_state
+            .abandon_game(id)
+            .unwrap();
+
+        assert_eq!(
+            app_state.games().count(),
+            0
+        );
+        assert_eq!(
+            app_state.stats().played(),
+            1
+        );
+        assert_eq!(
+            app_state.stats().won(),
+            0
+        );
+
+        let actual =
+            app_state.abandon_game(id);
+        let expected =
+            AppError::GameNotInProgress(
+                id,
             );
 
-        let mut app_state = AppState(
-            Some(dummy_game_state),
+        assert_app_error!(
+            actual, expected
         );
-        app_state.set_empty();
+    }
+
+    // fn end_game
+    #[test]
+    fn end_game_should_count_a_finished_game_in_the_stats(
+    ) {
+        let mut app_state =
+            AppState::empty();
 
-        assert!(app_state.0.is_none());
+        let (id, game_state) = app_state
+            .new_game_with(
+                &mut FixedPicker::new(
+                    DUMMY,
+                ),
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+        let attempt =
+            game_state.score(DUMMY);
+        game_state.add_attempt(attempt);
+
+        app_state.end_game(id);
+
+        assert!(app_state
+            .game(id)
+            .is_err());
+        assert_eq!(
+            app_state.stats().won(),
+            1
+        );
+        assert_eq!(
+            app_state
+                .history()
+                .last()
+                .unwrap()
+                .id(),
+            id
+        );
+    }
+
+    #[test]
+    fn end_game_should_not_count_an_unfinished_game(
+    ) {
+        let mut app_state =
+            AppState::empty();
+
+        let id = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        )
+        .unwrap();
+
+        app_state.end_game(id);
+
+        assert!(app_state
+            .game(id)
+            .is_err());
+        assert_eq!(
+            app_state.stats().played(),
+            0
+        );
+    }
+
+    // fn drop_stale_games
+    #[test]
+    fn drop_stale_games_should_drop_only_games_left_alone_too_long(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                5,
+                Duration::minutes(30),
+            )
+            .unwrap(),
+        );
+
+        let stale = new_dummy_game(
+            &mut app_state,
+            &clock,
+        )
+        .unwrap();
+        clock.advance(
+            Duration::minutes(20),
+        );
+        let fresh = new_dummy_game(
+            &mut app_state,
+            &clock,
+        )
+        .unwrap();
+        clock.advance(
+            Duration::minutes(20),
+        );
+
+        let actual = app_state
+            .drop_stale_games();
+
+        assert_eq!(actual, vec![stale]);
+        assert_eq!(
+            app_state
+                .games()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![fresh]
+        );
+    }
+
+    // fn restore_game_from
+    #[test]
+    fn restore_game_from_should_add_the_game_of_the_snapshot(
+    ) {
+        let mut app_state =
+            AppState::empty();
+
+        let snapshot =
+            GameSnapshot::of(
+                &GameState::of(
+                    word_by_id(1)
+                        .unwrap(),
+                    GameConfig::default(
+                    ),
+                    &RealClock,
+                ),
+            )
+            .unwrap();
+
+        let first = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        )
+        .unwrap();
+
+        let (id, game_state) =
+            app_state
+                .restore_game_from(
+                    &snapshot,
+                    &RealClock,
+                )
+                .unwrap();
+
+        assert_eq!(
+            game_state.word(),
+            word_by_id(1).unwrap()
+        );
+        assert_ne!(id, first);
+        assert_eq!(
+            app_state.games().count(),
+            2
+        );
+    }
+
+    // fn new_daily_game_with
+    #[test]
+    fn new_daily_game_with_should_give_every_player_the_same_word_on_the_same_day(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 24, 9, 30,
+        );
+        let schedule =
+            DailySchedule::default();
+
+        let mut first_player =
+            AppState::empty();
+        let mut second_player =
+            AppState::empty();
+
+        let (_, first) = first_player
+            .new_daily_game_with(
+                &schedule,
+                GameConfig::default(),
+                &clock,
+            )
+            .unwrap();
+        let (_, second) = second_player
+            .new_daily_game_with(
+                &schedule,
+                GameConfig::default(),
+                &clock,
+            )
+            .unwrap();
+
+        assert_eq!(
+            first.word(),
+            second.word()
+        );
+        assert_eq!(
+            first.puzzle_number(),
+            Some(115)
+        );
     }
 
     // fn new_game_with
@@ -84,18 +878,372 @@ mod tests {
         let mut app_state =
             AppState::empty();
 
-        assert!(app_state.0.is_none());
+        assert_eq!(
+            app_state.games().count(),
+            0
+        );
 
-        let game_state = app_state
+        let (id, game_state) = app_state
             .new_game_with(
-                || Ok(DUMMY),
+                &mut FixedPicker::new(
+                    DUMMY,
+                ),
+                GameConfig::default(),
                 &RealClock,
             )
             .unwrap();
 
+        assert_eq!(id, 1);
         assert_eq!(
             game_state.word(),
             DUMMY
         );
+        assert_eq!(
+            *game_state.config(),
+            GameConfig::default()
+        );
+    }
+
+    #[test]
+    fn new_game_with_should_refuse_more_games_than_the_limit(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                2,
+                Duration::days(1),
+            )
+            .unwrap(),
+        );
+
+        for _ in 0..2 {
+            new_dummy_game(
+                &mut app_state,
+                &RealClock,
+            )
+            .unwrap();
+        }
+
+        let actual = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        );
+        let expected =
+            AppError::TooManyGames(2);
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn new_multi_game_with
+    #[test]
+    fn new_multi_game_with_should_count_towards_the_limit(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                2,
+                Duration::days(1),
+            )
+            .unwrap(),
+        );
+        let id = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        )
+        .unwrap();
+
+        let (multi_id, game) = app_state
+            .new_multi_game_with(
+                &mut SeededPicker::new(7),
+                2,
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+
+        assert_eq!(
+            (id, multi_id),
+            (1, 2)
+        );
+        assert_eq!(
+            game.boards().len(),
+            2
+        );
+
+        let actual = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        );
+        let expected =
+            AppError::TooManyGames(2);
+
+        assert_app_error!(
+            actual, expected
+        );
+
+        app_state
+            .abandon_game(multi_id)
+            .unwrap();
+
+        let actual = app_state
+            .multi_game(multi_id)
+            .map(|_| ());
+        let expected =
+            AppError::GameNotInProgress(
+                multi_id,
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(
+            app_state.stats().played(),
+            0
+        );
+    }
+
+    #[test]
+    fn new_game_with_should_not_pick_a_word_when_there_is_no_room(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                1,
+                Duration::days(1),
+            )
+            .unwrap(),
+        );
+        let id = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        )
+        .unwrap();
+        let mut picker =
+            SequentialPicker::new();
+
+        let actual = app_state
+            .new_game_with(
+                &mut picker,
+                GameConfig::default(),
+                &RealClock,
+            )
+            .map(|_| ());
+        let expected =
+            AppError::TooManyGames(1);
+
+        assert_app_error!(
+            actual, expected
+        );
+
+        app_state
+            .abandon_game(id)
+            .unwrap();
+        let (_, game_state) = app_state
+            .new_game_with(
+                &mut picker,
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+
+        assert_eq!(
+            Some(game_state.word()),
+            SequentialPicker::new()
+                .pick(
+                    &GameConfig::default()
+                )
+                .ok()
+        );
+    }
+
+    // fn join_race_with
+    #[test]
+    fn join_race_with_should_start_a_new_race_once_the_last_one_is_over(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        let mut picker =
+            FixedPicker::new(DUMMY);
+
+        for name in ["alice", "bob"] {
+            app_state
+                .join_race_with(
+                    name,
+                    &mut picker,
+                    GameConfig::default(
+                    ),
+                    &RealClock,
+                )
+                .unwrap();
+        }
+
+        let race = app_state
+            .race_as_mut()
+            .unwrap();
+        race.guess("alice", DUMMY)
+            .unwrap();
+
+        assert_eq!(race.id(), 1);
+        assert_eq!(
+            race.winner(),
+            Some("alice")
+        );
+
+        let race = app_state
+            .join_race_with(
+                "bob",
+                &mut picker,
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+
+        assert_eq!(race.id(), 2);
+        assert_eq!(race.winner(), None);
+        assert_eq!(
+            race.standings().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn join_race_with_should_count_the_race_towards_the_limit(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                1,
+                Duration::days(1),
+            )
+            .unwrap(),
+        );
+        app_state
+            .join_race_with(
+                "alice",
+                &mut FixedPicker::new(
+                    DUMMY,
+                ),
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+
+        let actual = new_dummy_game(
+            &mut app_state,
+            &RealClock,
+        );
+        let expected =
+            AppError::TooManyGames(1);
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn join_race_with_should_start_over_once_the_race_is_stale(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut app_state =
+            AppState::empty();
+        app_state.set_limits(
+            GameLimits::new(
+                5,
+                Duration::minutes(30),
+            )
+            .unwrap(),
+        );
+        let mut picker =
+            FixedPicker::new(DUMMY);
+
+        app_state
+            .join_race_with(
+                "alice",
+                &mut picker,
+                GameConfig::default(),
+                &clock,
+            )
+            .unwrap();
+        clock.advance(
+            Duration::minutes(40),
+        );
+
+        assert_eq!(
+            app_state
+                .drop_stale_games(),
+            vec![1]
+        );
+
+        let race = app_state
+            .join_race_with(
+                "bob",
+                &mut picker,
+                GameConfig::default(),
+                &clock,
+            )
+            .unwrap();
+
+        assert_eq!(race.id(), 2);
+        assert_eq!(
+            race.standings().len(),
+            1
+        );
+    }
+
+    // fn new_challenge_game
+    #[test]
+    fn new_challenge_game_should_report_the_result_to_the_challenge(
+    ) {
+        let mut app_state =
+            AppState::empty();
+        let word =
+            word_by_id(1).unwrap();
+        let code = app_state
+            .create_challenge(
+                word, &RealClock,
+            )
+            .unwrap()
+            .code()
+            .to_string();
+
+        let (id, game_state) =
+            app_state
+                .new_challenge_game(
+                    &code,
+                    GameConfig::default(
+                    ),
+                    &RealClock,
+                )
+                .unwrap();
+        let attempt =
+            game_state.score(word);
+        game_state.add_attempt(attempt);
+
+        assert_eq!(
+            game_state.word(),
+            word
+        );
+
+        app_state.end_game(id);
+
+        let results = app_state
+            .challenge(
+                &code, &RealClock,
+            )
+            .unwrap()
+            .results();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].game_id(),
+            id
+        );
     }
 }
@@ -0,0 +1,404 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    char_result::CharResult,
+    core::{
+        char_map_from, letter_count,
+        score_guess,
+    },
+    solver::Solver,
+};
+use error_stack::bail;
+use std::collections::BTreeMap;
+
+/// Picks the next guess of a benchmarked game from the words that
+/// fit the guesses so far.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// The next guess, or `None` to give up on the word.
+    fn next_guess(
+        &self,
+        solver: &Solver,
+        attempts: &[Vec<CharResult>],
+    ) -> Option<&'static str>;
+}
+
+/// Plays the guess expected to tell the most about the word.
+pub struct MaxEntropy;
+impl Strategy for MaxEntropy {
+    fn name(&self) -> &'static str {
+        "max-entropy"
+    }
+
+    fn next_guess(
+        &self,
+        solver: &Solver,
+        attempts: &[Vec<CharResult>],
+    ) -> Option<&'static str> {
+        solver
+            .suggest(attempts)
+            .map(|s| s.word())
+    }
+}
+
+/// Plays the first word of the list that could still be the word.
+pub struct FirstCandidate;
+impl Strategy for FirstCandidate {
+    fn name(&self) -> &'static str {
+        "first-candidate"
+    }
+
+    fn next_guess(
+        &self,
+        solver: &Solver,
+        attempts: &[Vec<CharResult>],
+    ) -> Option<&'static str> {
+        solver
+            .candidates(attempts)
+            .first()
+            .copied()
+    }
+}
+
+/// A word the strategy did not find within the allowed attempts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unsolved {
+    word: &'static str,
+    guesses: Vec<&'static str>,
+}
+impl Unsolved {
+    pub fn word(&self) -> &'static str {
+        self.word
+    }
+
+    pub fn guesses(
+        &self,
+    ) -> &[&'static str] {
+        &self.guesses
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchReport {
+    strategy: &'static str,
+    max_attempts: usize,
+    distribution:
+        BTreeMap<usize, usize>,
+    unsolved: Vec<Unsolved>,
+}
+impl BenchReport {
+    /// Plays every word of the list against the words of its length.
+    pub fn run(
+        words: &[&'static str],
+        strategy: &dyn Strategy,
+        max_attempts: usize,
+    ) -> Self {
+        let mut solvers: BTreeMap<
+            usize,
+            Solver,
+        > = BTreeMap::new();
+        let mut report = Self {
+            strategy: strategy.name(),
+            max_attempts,
+            distribution: BTreeMap::new(
+            ),
+            unsolved: vec![],
+        };
+
+        for word in words {
+            let solver = solvers
+                .entry(letter_count(word))
+                .or_insert_with_key(
+                    |length| {
+                        Solver::from_words(
+                            words, *length,
+                        )
+                    },
+                );
+
+            let guesses = play(
+                word,
+                solver,
+                strategy,
+                max_attempts,
+            );
+
+            if guesses.last()
+                == Some(word)
+            {
+                *report
+                    .distribution
+                    .entry(
+                        guesses.len(),
+                    )
+                    .or_default() += 1;
+            } else {
+                report.unsolved.push(
+                    Unsolved {
+                        word,
+                        guesses,
+                    },
+                );
+            }
+        }
+
+        report
+    }
+
+    pub fn strategy(
+        &self,
+    ) -> &'static str {
+        self.strategy
+    }
+
+    pub fn max_attempts(
+        &self,
+    ) -> usize {
+        self.max_attempts
+    }
+
+    /// Number of solved words per number of guesses used.
+    pub fn distribution(
+        &self,
+    ) -> &BTreeMap<usize, usize> {
+        &self.distribution
+    }
+
+    pub fn unsolved(
+        &self,
+    ) -> &[Unsolved] {
+        &self.unsolved
+    }
+
+    pub fn solved_count(
+        &self,
+    ) -> usize {
+        self.distribution.values().sum()
+    }
+
+    pub fn average_guesses(
+        &self,
+    ) -> Option<f64> {
+        let solved =
+            self.solved_count();
+
+        (solved > 0).then(|| {
+            self.distribution
+                .iter()
+                .map(|(guesses, count)| {
+                    guesses * count
+                })
+                .sum::<usize>()
+                as f64
+                / solved as f64
+        })
+    }
+
+    /// Turns the first word that was not solved into an error.
+    pub fn strict(
+        &self,
+    ) -> AppResult<()> {
+        if let Some(unsolved) =
+            self.unsolved.first()
+        {
+            bail!(
+                AppError::UnsolvedWord(
+                    unsolved
+                        .word
+                        .to_string()
+                )
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        let mut result = vec![format!(
+            "Played {} words with the {} strategy: {} solved, {} not solved in {} attempts.",
+            self.solved_count()
+                + self.unsolved.len(),
+            self.strategy,
+            self.solved_count(),
+            self.unsolved.len(),
+            self.max_attempts
+        )];
+
+        if let Some(average) =
+            self.average_guesses()
+        {
+            result.push(format!(
+                "Average guesses: {:.2}",
+                average
+            ));
+        }
+
+        result.push(
+            "Guesses:".to_string(),
+        );
+        result.extend(
+            (1..=self.max_attempts).map(
+                |guesses| {
+                    format!(
+                        "  {:>2}: {}",
+                        guesses,
+                        self.distribution
+                            .get(&guesses)
+                            .unwrap_or(&0)
+                    )
+                },
+            ),
+        );
+
+        result.extend(
+            self.unsolved.iter().map(
+                |u| {
+                    format!(
+                        "Not solved: '{}' after {}",
+                        u.word,
+                        u.guesses.join(", ")
+                    )
+                },
+            ),
+        );
+
+        result
+    }
+}
+
+fn play(
+    word: &str,
+    solver: &Solver,
+    strategy: &dyn Strategy,
+    max_attempts: usize,
+) -> Vec<&'static str> {
+    let char_map = char_map_from(word);
+    let mut attempts = vec![];
+    let mut guesses = vec![];
+
+    while guesses.len() < max_attempts {
+        let Some(guess) = strategy
+            .next_guess(
+                solver, &attempts,
+            )
+        else {
+            break;
+        };
+
+        guesses.push(guess);
+
+        if guess == word {
+            break;
+        }
+
+        attempts.push(score_guess(
+            guess, &char_map,
+        ));
+    }
+
+    guesses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_app_error;
+    use pretty_assertions::assert_eq;
+
+    const WORDS: &[&str] = &[
+        "golem", "globe", "gloom",
+        "lemon", "melon", "spice",
+        "wasm",
+    ];
+
+    // fn run()
+    #[test]
+    fn run_should_solve_every_word_with_max_entropy(
+    ) {
+        let report = BenchReport::run(
+            WORDS,
+            &MaxEntropy,
+            6,
+        );
+
+        assert_eq!(
+            report.solved_count(),
+            WORDS.len()
+        );
+        assert!(report
+            .unsolved()
+            .is_empty());
+        assert!(report
+            .strict()
+            .is_ok());
+        // "wasm" is alone in its length, the others split quickly
+        assert!(report
+            .distribution()
+            .keys()
+            .all(
+                |guesses| *guesses <= 3
+            ));
+    }
+
+    #[test]
+    fn run_should_report_the_words_not_solved_in_time(
+    ) {
+        // the first candidate is always the earliest word of the list
+        let report = BenchReport::run(
+            WORDS,
+            &FirstCandidate,
+            1,
+        );
+
+        assert_eq!(
+            report
+                .unsolved()
+                .iter()
+                .map(|u| u.word())
+                .collect::<Vec<_>>(),
+            vec![
+                "globe", "gloom",
+                "lemon", "melon",
+                "spice"
+            ]
+        );
+        assert_eq!(
+            report.unsolved()[0]
+                .guesses(),
+            ["golem"]
+        );
+
+        let actual = report.strict();
+        let expected =
+            AppError::UnsolvedWord(
+                "globe".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn describe()
+    #[test]
+    fn describe_should_summarize_the_guess_distribution(
+    ) {
+        let actual = BenchReport::run(
+            &["golem", "lemon"],
+            &FirstCandidate,
+            2,
+        )
+        .describe();
+        let expected = vec![
+            "Played 2 words with the first-candidate strategy: 2 solved, 0 not solved in 2 attempts.",
+            "Average guesses: 1.50",
+            "Guesses:",
+            "   1: 1",
+            "   2: 1",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    char_result::CharResult,
+    core::{
+        char_map_from, score_guess,
+        GuessResult,
+    },
+    solver::Solver,
+};
+use std::{
+    cmp::Reverse, collections::BTreeMap,
+};
+
+/// The words that still fit every guess of an adversarial game. No
+/// word is chosen up front: each guess is answered with the feedback
+/// that keeps the most of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CandidateSet {
+    candidates: Vec<&'static str>,
+}
+impl CandidateSet {
+    /// Uses the words of the current word list with this many letters
+    /// that would have scored every attempt the same way.
+    pub fn new(
+        word_length: usize,
+        attempts: &[Vec<CharResult>],
+    ) -> Self {
+        Self {
+            candidates: Solver::new(
+                word_length,
+            )
+            .candidates(attempts),
+        }
+    }
+
+    pub fn from_words(
+        words: &[&'static str],
+    ) -> Self {
+        Self {
+            candidates: words.to_vec(),
+        }
+    }
+
+    pub fn candidates(
+        &self,
+    ) -> &[&'static str] {
+        &self.candidates
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    /// Keeps the largest group of candidates that score the guess the
+    /// same way, and answers one of them to score the guess against.
+    /// On a tie the group that tells the least wins, so the guess
+    /// itself is only answered once it is the last candidate.
+    pub fn answer(
+        &mut self,
+        guess: &str,
+    ) -> Option<&'static str> {
+        let mut groups: BTreeMap<
+            Vec<u8>,
+            Vec<&'static str>,
+        > = BTreeMap::new();
+
+        for word in &self.candidates {
+            let pattern = score_guess(
+                guess,
+                &char_map_from(word),
+            )
+            .iter()
+            .map(|r| match r.result() {
+                GuessResult::Absent => 0,
+                GuessResult::Present => 1,
+                GuessResult::Correct => 2,
+            })
+            .collect();
+
+            groups
+                .entry(pattern)
+                .or_default()
+                .push(word);
+        }
+
+        let (_, words) = groups
+            .into_iter()
+            .max_by_key(|(pattern, words)| {
+                (
+                    words.len(),
+                    Reverse(
+                        pattern
+                            .iter()
+                            .map(|&n| {
+                                usize::from(n)
+                            })
+                            .sum::<usize>(),
+                    ),
+                    Reverse(pattern.clone()),
+                )
+            })?;
+
+        self.candidates = words;
+
+        self.candidates.first().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // fn answer()
+    #[test]
+    fn answer_should_keep_the_largest_group_of_candidates(
+    ) {
+        let mut set =
+            CandidateSet::from_words(
+                &[
+                    "golem", "hotel",
+                    "spice", "slice",
+                    "space",
+                ],
+            );
+
+        let actual =
+            set.answer("spice");
+
+        assert_eq!(
+            actual,
+            Some("golem")
+        );
+        assert_eq!(
+            set.candidates(),
+            &["golem", "hotel"]
+        );
+    }
+
+    #[test]
+    fn answer_should_only_give_in_on_the_last_candidate(
+    ) {
+        let mut set =
+            CandidateSet::from_words(
+                &["golem", "gnome"],
+            );
+
+        assert_eq!(
+            set.answer("golem"),
+            Some("gnome")
+        );
+        assert_eq!(
+            set.answer("gnome"),
+            Some("gnome")
+        );
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn answer_should_give_nothing_without_candidates(
+    ) {
+        let mut set =
+            CandidateSet::from_words(
+                &[],
+            );
+
+        assert_eq!(
+            set.answer("golem"),
+            None
+        );
+    }
+}
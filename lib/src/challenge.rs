@@ -0,0 +1,530 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    clock::{
+        format_duration, Clock, Gmt,
+    },
+    core::{
+        letter_count, normalized,
+        random_number, word_by_id,
+        word_id_of,
+    },
+    game_history::GameOutcome,
+    game_state::GameState,
+};
+use chrono::Duration;
+use error_stack::bail;
+use std::collections::BTreeMap;
+
+pub const CHALLENGE_CODE_LENGTH: usize =
+    8;
+
+/// No vowels, so that a code never spells a word, and none of the
+/// digits that look like letters.
+const CODE_ALPHABET: &[u8] =
+    b"bcdfghjkmnpqrstvwxz23456789";
+
+/// How one game of a challenge went.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChallengeResult {
+    game_id: u64,
+    outcome: GameOutcome,
+    guesses: usize,
+    hints: usize,
+    time: Duration,
+    ended_at: Gmt,
+}
+impl ChallengeResult {
+    fn of(
+        game_id: u64,
+        game_state: &GameState,
+    ) -> Self {
+        Self {
+            game_id,
+            outcome: GameOutcome::of(
+                game_state,
+            ),
+            guesses: game_state
+                .attempts()
+                .len(),
+            hints: game_state
+                .hints()
+                .len(),
+            time: game_state.elapsed(),
+            ended_at: game_state
+                .last_update(),
+        }
+    }
+
+    pub fn game_id(&self) -> u64 {
+        self.game_id
+    }
+
+    pub fn outcome(
+        &self,
+    ) -> GameOutcome {
+        self.outcome
+    }
+
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    pub fn hints(&self) -> usize {
+        self.hints
+    }
+
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn ended_at(&self) -> Gmt {
+        self.ended_at
+    }
+
+    /// Tells how the game went without giving the word away.
+    pub fn describe(&self) -> String {
+        let outcome = match self.outcome
+        {
+            GameOutcome::Won => {
+                format!("solved in {} guesses", self.guesses)
+            }
+            GameOutcome::Lost => {
+                format!("not solved in {} guesses", self.guesses)
+            }
+            GameOutcome::TimedOut => {
+                format!("ran out of time after {} guesses", self.guesses)
+            }
+        };
+        let outcome = match self.hints {
+            0 => outcome,
+            1 => format!("{outcome} with 1 hint"),
+            n => format!("{outcome} with {n} hints"),
+        };
+
+        format!(
+            "Game {}: {}, {}.",
+            self.game_id,
+            outcome,
+            format_duration(self.time)
+        )
+    }
+}
+
+/// A word chosen by a player for others to guess, known by a code
+/// that does not give the word away.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Challenge {
+    code: String,
+    word: &'static str,
+    created_at: Gmt,
+    results: Vec<ChallengeResult>,
+}
+impl Challenge {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub(crate) fn word(
+        &self,
+    ) -> &'static str {
+        self.word
+    }
+
+    pub fn word_length(&self) -> usize {
+        letter_count(self.word)
+    }
+
+    pub fn created_at(&self) -> Gmt {
+        self.created_at
+    }
+
+    /// When the last game of the challenge ended, or else when it was
+    /// created.
+    pub fn last_update(&self) -> Gmt {
+        self.results.last().map_or(
+            self.created_at,
+            ChallengeResult::ended_at,
+        )
+    }
+
+    /// The finished games of the challenge, oldest first.
+    pub fn results(
+        &self,
+    ) -> &[ChallengeResult] {
+        &self.results
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        let solved = self
+            .results
+            .iter()
+            .filter(|r| {
+                r.outcome
+                    == GameOutcome::Won
+            })
+            .count();
+
+        let mut result = vec![
+            format!(
+                "Challenge {}: a {}-letter word, created on {}.",
+                self.code,
+                self.word_length(),
+                self.created_at
+            ),
+            format!(
+                "Played {} times, solved {} times.",
+                self.results.len(),
+                solved
+            ),
+        ];
+
+        result.extend(
+            self.results
+                .iter()
+                .map(ChallengeResult::describe),
+        );
+
+        result
+    }
+}
+
+/// The challenges created on this worker, and the games in progress
+/// that play one of them.
+#[derive(Debug, Default)]
+pub struct Challenges {
+    by_code:
+        BTreeMap<String, Challenge>,
+    games: BTreeMap<u64, String>,
+}
+impl Challenges {
+    pub(crate) const fn new() -> Self {
+        Self {
+            by_code: BTreeMap::new(),
+            games: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a challenge for a word of the word list.
+    pub fn create(
+        &mut self,
+        word: &str,
+        clock: &impl Clock,
+    ) -> AppResult<&Challenge> {
+        let word =
+            normalized(word.trim());
+
+        let Some(word) =
+            word_id_of(&word)
+                .and_then(word_by_id)
+        else {
+            bail!(
+                AppError::UnknownWord(
+                    word
+                )
+            )
+        };
+
+        let code = loop {
+            let code = new_code();
+
+            if !self
+                .by_code
+                .contains_key(&code)
+            {
+                break code;
+            }
+        };
+
+        Ok(self
+            .by_code
+            .entry(code.clone())
+            .or_insert(Challenge {
+                code,
+                word,
+                created_at: clock.now(),
+                results: vec![],
+            }))
+    }
+
+    pub fn get(
+        &self,
+        code: &str,
+    ) -> AppResult<&Challenge> {
+        let code =
+            code.trim().to_lowercase();
+
+        match self.by_code.get(&code) {
+            Some(challenge) => Ok(challenge),
+            None => bail!(AppError::ChallengeNotFound(
+                code
+            )),
+        }
+    }
+
+    /// Drops the challenges nobody has created, played or finished a
+    /// game of for longer than the duration, answering their codes.
+    /// A challenge with a game in progress is kept.
+    pub(crate) fn drop_stale(
+        &mut self,
+        now: Gmt,
+        stale_after: &Duration,
+    ) -> Vec<String> {
+        let stale: Vec<_> = self
+            .by_code
+            .values()
+            .filter(|c| {
+                now - c.last_update()
+                    > *stale_after
+                    && !self
+                        .games
+                        .values()
+                        .any(|code| {
+                            *code == c.code
+                        })
+            })
+            .map(|c| c.code.clone())
+            .collect();
+
+        for code in &stale {
+            self.by_code.remove(code);
+        }
+
+        stale
+    }
+
+    pub(crate) fn track(
+        &mut self,
+        game_id: u64,
+        code: &str,
+    ) {
+        self.games.insert(
+            game_id,
+            code.to_string(),
+        );
+    }
+
+    pub(crate) fn untrack(
+        &mut self,
+        game_id: u64,
+    ) {
+        self.games.remove(&game_id);
+    }
+
+    /// Adds the result of a finished game to its challenge, if it
+    /// played one.
+    pub(crate) fn record(
+        &mut self,
+        game_id: u64,
+        game_state: &GameState,
+    ) {
+        let challenge = self
+            .games
+            .remove(&game_id)
+            .and_then(|code| {
+                self.by_code
+                    .get_mut(&code)
+            });
+
+        if let Some(challenge) =
+            challenge
+        {
+            challenge.results.push(
+                ChallengeResult::of(
+                    game_id, game_state,
+                ),
+            );
+        }
+    }
+}
+
+fn new_code() -> String {
+    (0..CHALLENGE_CODE_LENGTH)
+        .map(|_| {
+            CODE_ALPHABET[random_number(
+                CODE_ALPHABET.len(),
+            )] as char
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::{
+            tests::TestClock, RealClock,
+        },
+        game_config::GameConfig,
+    };
+    use pretty_assertions::assert_eq;
+
+    // fn create()
+    #[test]
+    fn create_should_hide_the_word_behind_a_code(
+    ) {
+        let word =
+            word_by_id(0).unwrap();
+        let mut challenges =
+            Challenges::new();
+
+        let challenge = challenges
+            .create(
+                &word.to_uppercase(),
+                &RealClock,
+            )
+            .unwrap();
+        let code = challenge
+            .code()
+            .to_string();
+
+        assert_eq!(
+            challenge.word(),
+            word
+        );
+        assert_eq!(
+            code.len(),
+            CHALLENGE_CODE_LENGTH
+        );
+        assert!(!code.contains(word));
+        assert_eq!(
+            challenges
+                .get(
+                    &code
+                        .to_uppercase()
+                )
+                .unwrap()
+                .word(),
+            word
+        );
+    }
+
+    #[test]
+    fn create_should_refuse_a_word_that_is_not_in_the_word_list(
+    ) {
+        let mut challenges =
+            Challenges::new();
+
+        let actual = challenges
+            .create("zzzzz", &RealClock)
+            .map(|_| ());
+        let expected =
+            AppError::UnknownWord(
+                "zzzzz".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn drop_stale()
+    #[test]
+    fn drop_stale_should_keep_challenges_still_played(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut challenges =
+            Challenges::new();
+        let mut create = || {
+            challenges
+                .create(
+                    word_by_id(0)
+                        .unwrap(),
+                    &clock,
+                )
+                .unwrap()
+                .code()
+                .to_string()
+        };
+        let stale = create();
+        let played = create();
+        challenges.track(7, &played);
+
+        clock
+            .advance(Duration::days(2));
+        let fresh = challenges
+            .create(
+                word_by_id(0).unwrap(),
+                &clock,
+            )
+            .unwrap()
+            .code()
+            .to_string();
+
+        let actual = challenges
+            .drop_stale(
+                clock.now(),
+                &Duration::days(1),
+            );
+
+        assert_eq!(
+            actual,
+            vec![stale.clone()]
+        );
+        assert!(challenges
+            .get(&stale)
+            .is_err());
+        assert!(challenges
+            .get(&played)
+            .is_ok());
+        assert!(challenges
+            .get(&fresh)
+            .is_ok());
+    }
+
+    // fn record()
+    #[test]
+    fn record_should_only_count_games_played_on_the_challenge(
+    ) {
+        let mut challenges =
+            Challenges::new();
+        let code = challenges
+            .create(
+                word_by_id(0).unwrap(),
+                &RealClock,
+            )
+            .unwrap()
+            .code()
+            .to_string();
+        let challenge = challenges
+            .get(&code)
+            .unwrap();
+
+        let mut game_state =
+            GameState::of(
+                challenge.word(),
+                GameConfig::default(),
+                &RealClock,
+            );
+        let attempt = game_state
+            .score(challenge.word());
+        game_state.add_attempt(attempt);
+
+        challenges.track(7, &code);
+        challenges
+            .record(7, &game_state);
+        challenges
+            .record(8, &game_state);
+
+        let actual: Vec<_> = challenges
+            .get(&code)
+            .unwrap()
+            .results()
+            .iter()
+            .map(|r| {
+                (
+                    r.game_id(),
+                    r.outcome(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![(7, GameOutcome::Won)]
+        );
+    }
+}
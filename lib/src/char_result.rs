@@ -1,21 +1,32 @@
 use crate::core::GuessResult;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug, PartialEq)]
-pub(crate) struct CharResult {
-    char: char,
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct CharResult {
+    #[serde(alias = "char")]
+    letter: String,
     result: GuessResult,
 }
 
 impl CharResult {
     pub(crate) fn new(
-        char: char,
+        letter: impl Into<String>,
         result: GuessResult,
     ) -> Self {
-        Self { char, result }
+        Self {
+            letter: letter.into(),
+            result,
+        }
     }
 
-    pub(crate) fn display(
+    pub fn display(
         attempt: &[CharResult],
     ) -> String {
         let joined = attempt
@@ -26,6 +37,18 @@ impl CharResult {
 
         format!("[{}]", joined)
     }
+
+    /// One letter as the player sees it, which may take more than
+    /// one `char`.
+    pub fn letter(&self) -> &str {
+        &self.letter
+    }
+
+    pub fn result(
+        &self,
+    ) -> &GuessResult {
+        &self.result
+    }
 }
 
 impl Display for CharResult {
@@ -36,7 +59,7 @@ impl Display for CharResult {
         write!(
             f,
             "'{}' => {:?}",
-            self.char, self.result
+            self.letter, self.result
         )
     }
 }
@@ -49,28 +72,28 @@ mod tests {
 
     impl CharResult {
         pub(crate) fn correct(
-            char: char,
+            letter: impl Into<String>,
         ) -> Self {
             Self::new(
-                char,
+                letter,
                 GuessResult::Correct,
             )
         }
 
         pub(crate) fn absent(
-            char: char,
+            letter: impl Into<String>,
         ) -> Self {
             Self::new(
-                char,
+                letter,
                 GuessResult::Absent,
             )
         }
 
         pub(crate) fn present(
-            char: char,
+            letter: impl Into<String>,
         ) -> Self {
             Self::new(
-                char,
+                letter,
                 GuessResult::Present,
             )
         }
@@ -86,6 +109,14 @@ mod tests {
         "['a' => Correct, 'b' => Absent, 'c' => Present]" ;
         "Non-empty Vec<CharResult> should result in a readable string"
     )]
+    #[test_case(
+        &[
+            CharResult::correct("n\u{303}"),
+            CharResult::present('ü'),
+        ],
+        "['n\u{303}' => Correct, 'ü' => Present]" ;
+        "Letters made of several chars are shown as one letter"
+    )]
     #[test_case(
         &vec![],
         "[]" ;
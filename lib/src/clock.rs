@@ -1,6 +1,32 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use std::cell::Cell;
 
-pub(crate) type Gmt = DateTime<Utc>;
+pub type Gmt = DateTime<Utc>;
+
+/// Formats a duration as "m:ss", or "h:mm:ss" from an hour on.
+pub fn format_duration(
+    duration: Duration,
+) -> String {
+    let seconds =
+        duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+
+    if hours > 0 {
+        format!(
+            "{}:{:0>2}:{:0>2}",
+            hours, minutes, seconds
+        )
+    } else {
+        format!(
+            "{}:{:0>2}",
+            minutes, seconds
+        )
+    }
+}
 
 pub trait Clock {
     fn now(&self) -> Gmt;
@@ -14,12 +40,37 @@ impl Clock for RealClock {
     }
 }
 
+/// A clock that only moves when told to, used to play a game again
+/// at the times it was first played.
+pub struct ManualClock {
+    time: Cell<Gmt>,
+}
+
+impl ManualClock {
+    pub fn new(time: Gmt) -> Self {
+        Self {
+            time: Cell::new(time),
+        }
+    }
+
+    pub fn set(&self, time: Gmt) {
+        self.time.set(time);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Gmt {
+        self.time.get()
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
     use chrono::{
         offset::TimeZone, Duration, Utc,
     };
+    use pretty_assertions::assert_eq;
     use std::cell::RefCell;
 
     pub(crate) struct TestClock {
@@ -89,4 +140,28 @@ pub(crate) mod tests {
                 .to_owned()
         }
     }
+
+    // fn format_duration()
+    #[test]
+    fn format_duration_should_show_hours_only_when_needed(
+    ) {
+        assert_eq!(
+            format_duration(
+                Duration::seconds(83)
+            ),
+            "1:23"
+        );
+        assert_eq!(
+            format_duration(
+                Duration::seconds(3723)
+            ),
+            "1:02:03"
+        );
+        assert_eq!(
+            format_duration(
+                Duration::seconds(-5)
+            ),
+            "0:00"
+        );
+    }
 }
@@ -4,28 +4,41 @@ use crate::{
         AppResultExt,
     },
     app_state::AppState,
+    char_result::CharResult,
+    game_config::GameConfig,
+    word_list::with_words,
 };
 use error_stack::{bail, ResultExt};
 use once_cell::sync::Lazy;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
+    ops::RangeInclusive,
     str::from_utf8,
 };
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub(crate) const WORDS_FILE_PATH: &str =
     "assets/words.txt";
 
-#[allow(clippy::unwrap_used)]
-static WORDS: Lazy<Vec<String>> =
-    Lazy::new(|| {
-        load_words()
-            .err_as_string()
-            .unwrap()
-    });
+/// The lengths of the words in the allowed guesses dictionary.
+/// Guesses of any other length are let through, as the dictionary has
+/// no words to check them against.
+pub(crate) const ALLOWED_GUESS_LENGTHS:
+    RangeInclusive<usize> = 4..=8;
 
-static WORD_POOL_SIZE: Lazy<usize> =
-    Lazy::new(|| WORDS.len());
+#[allow(clippy::unwrap_used)]
+static ALLOWED_GUESSES: Lazy<
+    HashSet<String>,
+> = Lazy::new(|| {
+    load_allowed_guesses()
+        .err_as_string()
+        .unwrap()
+        .into_iter()
+        .collect()
+});
 
 static mut APP_STATE: AppState =
     AppState::empty();
@@ -39,15 +52,40 @@ pub fn with_app_state<T>(
     unsafe { f(&mut APP_STATE) }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub(crate) enum GuessResult {
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum GuessResult {
     Correct,
     Present,
     Absent,
 }
 
 pub(crate) type CharMap =
-    HashMap<char, HashSet<usize>>;
+    HashMap<String, HashSet<usize>>;
+
+/// Splits a word into the letters a player sees, so that "ñ" or "e"
+/// followed by a combining accent count as one letter.
+pub fn letters_of(
+    s: &str,
+) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+pub fn letter_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// The word in lowercase and in Unicode NFC, so that "ñ" is the same
+/// letter whether it was typed as one code point or as "n" followed
+/// by a combining tilde.
+pub fn normalized(s: &str) -> String {
+    s.to_lowercase().nfc().collect()
+}
 
 pub(crate) fn char_map_from(
     s: &str,
@@ -55,10 +93,12 @@ pub(crate) fn char_map_from(
     let mut hashmap: CharMap =
         HashMap::new();
 
-    for (i, c) in s.chars().enumerate()
+    for (i, c) in letters_of(s)
+        .into_iter()
+        .enumerate()
     {
         hashmap
-            .entry(c)
+            .entry(c.to_string())
             .and_modify(|set| {
                 set.insert(i);
             })
@@ -70,21 +110,108 @@ pub(crate) fn char_map_from(
     hashmap
 }
 
-fn words_from(
+pub(crate) fn score_guess(
+    guess: &str,
+    char_map: &CharMap,
+) -> Vec<CharResult> {
+    let chars = letters_of(guess);
+
+    let mut leftovers: HashMap<
+        &str,
+        usize,
+    > = char_map
+        .iter()
+        .map(|(c, positions)| {
+            (
+                c.as_str(),
+                positions.len(),
+            )
+        })
+        .collect();
+
+    // first pass: exact matches consume their letter
+    let exact_matches: Vec<_> = chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let is_correct = char_map
+                .get(*c)
+                .is_some_and(
+                    |positions| {
+                        positions
+                            .contains(
+                                &i,
+                            )
+                    },
+                );
+
+            if is_correct {
+                if let Some(count) =
+                    leftovers.get_mut(c)
+                {
+                    *count -= 1;
+                }
+            }
+
+            is_correct
+        })
+        .collect();
+
+    // second pass: Present only while the letter has unmatched occurrences left
+    chars
+        .iter()
+        .zip(exact_matches)
+        .map(|(c, is_correct)| {
+            let result = if is_correct {
+                GuessResult::Correct
+            } else {
+                match leftovers.get_mut(c) {
+                    Some(count)
+                        if *count > 0 =>
+                    {
+                        *count -= 1;
+                        GuessResult::Present
+                    }
+                    _ => GuessResult::Absent,
+                }
+            };
+
+            CharResult::new(*c, result)
+        })
+        .collect()
+}
+
+pub(crate) const BUILT_IN_WORDS:
+    &[u8] = include_bytes!(
+    "../../assets/words.txt"
+);
+
+pub(crate) fn text_from(
     bytes: &[u8],
-) -> AppResult<Vec<String>> {
-    let file_content = from_utf8(bytes)
-        .change_context(
-            AppError::InvalidCharset,
-        )?
-        .to_lowercase();
-
-    let words: Vec<_> = file_content
-        .lines()
+) -> AppResult<&str> {
+    from_utf8(bytes).change_context(
+        AppError::InvalidCharset,
+    )
+}
+
+pub(crate) fn entries_from(
+    text: &str,
+) -> impl Iterator<Item = &str> {
+    text.lines()
         .flat_map(|l| l.split(' '))
         .filter(|s| !{ s.is_empty() })
-        .map(|s| s.to_string())
-        .collect();
+}
+
+pub(crate) fn words_from(
+    bytes: &[u8],
+) -> AppResult<Vec<String>> {
+    let file_content =
+        normalized(text_from(bytes)?);
+
+    let words: Vec<_> =
+        entries_from(&file_content)
+            .map(|s| s.to_string())
+            .collect();
 
     if words.is_empty() {
         bail!(AppError::NoWords)
@@ -93,14 +220,52 @@ fn words_from(
 }
 
 pub(crate) fn load_words(
+) -> AppResult<Vec<String>> {
+    words_from(BUILT_IN_WORDS)
+}
+
+pub(crate) fn load_allowed_guesses(
 ) -> AppResult<Vec<String>> {
     let bytes: &[u8] = include_bytes!(
-        "../../assets/words.txt"
+        "../../assets/allowed_guesses.txt"
     );
 
     words_from(bytes)
 }
 
+pub(crate) fn is_allowed_guess(
+    guess: &str,
+) -> bool {
+    let length = letter_count(guess);
+
+    (length > 0
+        && !ALLOWED_GUESS_LENGTHS
+            .contains(&length))
+        || ALLOWED_GUESSES
+            .contains(guess)
+        || with_words(|words| {
+            words.contains(&guess)
+        })
+}
+
+pub(crate) fn word_id_of(
+    word: &str,
+) -> Option<usize> {
+    with_words(|words| {
+        words
+            .iter()
+            .position(|w| *w == word)
+    })
+}
+
+pub(crate) fn word_by_id(
+    id: usize,
+) -> Option<&'static str> {
+    with_words(|words| {
+        words.get(id).copied()
+    })
+}
+
 pub(crate) fn random_number(
     upper_bound: usize,
 ) -> usize {
@@ -108,13 +273,41 @@ pub(crate) fn random_number(
         .gen_range(0..upper_bound)
 }
 
+pub(crate) fn words_for(
+    config: &GameConfig,
+) -> AppResult<Vec<&'static str>> {
+    let candidates: Vec<_> = with_words(
+        |words| {
+            words
+                .iter()
+                .filter(|w| {
+                    config
+                        .allows_word_length(
+                            letter_count(w),
+                        )
+                })
+                .copied()
+                .collect()
+        },
+    );
+
+    if candidates.is_empty() {
+        bail!(AppError::NoWords)
+    }
+
+    Ok(candidates)
+}
+
 pub fn pick_word<'a>(
+    config: &GameConfig,
 ) -> AppResult<&'a str> {
+    let candidates = words_for(config)?;
+
     let index =
-        random_number(*WORD_POOL_SIZE);
+        random_number(candidates.len());
 
     if let Some(chosen_word) =
-        WORDS.get(index)
+        candidates.get(index)
     {
         // println!(
         //     "This word is chosen: {}",
@@ -134,8 +327,14 @@ mod tests {
     use error_stack::{report, Report};
     use maplit::{hashmap, hashset};
     use pretty_assertions::assert_eq;
-    use proptest::prelude::{
-        prop_assert, proptest,
+    use proptest::{
+        collection::vec,
+        prelude::{
+            prop_assert,
+            prop_assert_eq, proptest,
+            Strategy,
+        },
+        sample::select,
     };
     use test_case::test_case;
 
@@ -143,12 +342,20 @@ mod tests {
     #[test_case(
         "Hello",
         hashmap!{
-            'H' => hashset!{0},
-            'e' => hashset!{1},
-            'l' => hashset!{2, 3},
-            'o' => hashset!{4}
+            "H".to_string() => hashset!{0},
+            "e".to_string() => hashset!{1},
+            "l".to_string() => hashset!{2, 3},
+            "o".to_string() => hashset!{4}
         } ;
-        "a string is turned into a HashMap<char, HashSet<index_position>>"
+        "a string is turned into a HashMap<letter, HashSet<index_position>>"
+    )]
+    #[test_case(
+        "n\u{303}an\u{303}a",
+        hashmap!{
+            "n\u{303}".to_string() => hashset!{0, 2},
+            "a".to_string() => hashset!{1, 3}
+        } ;
+        "a letter with a combining accent keeps a single position"
     )]
     #[test_case(
         "",
@@ -157,7 +364,7 @@ mod tests {
     )]
     #[test_case(
         "   ",
-        hashmap!{ ' ' => hashset!{0, 1, 2} } ;
+        hashmap!{ " ".to_string() => hashset!{0, 1, 2} } ;
         "a string with just spaces is turned into a HashMap of just one entry"
     )]
     fn char_map_from_should_produce_char_map_from_string(
@@ -170,6 +377,199 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    // fn score_guess()
+    #[test_case(
+        "lllll",
+        "golem",
+        vec![
+            CharResult::absent('l'),
+            CharResult::absent('l'),
+            CharResult::correct('l'),
+            CharResult::absent('l'),
+            CharResult::absent('l'),
+        ] ;
+        "a repeated guess letter is only marked as many times as it occurs in the word"
+    )]
+    #[test_case(
+        "llama",
+        "golem",
+        vec![
+            CharResult::present('l'),
+            CharResult::absent('l'),
+            CharResult::absent('a'),
+            CharResult::present('m'),
+            CharResult::absent('a'),
+        ] ;
+        "only the first misplaced occurrence of a letter is marked as Present"
+    )]
+    #[test_case(
+        "eerie",
+        "elder",
+        vec![
+            CharResult::correct('e'),
+            CharResult::present('e'),
+            CharResult::present('r'),
+            CharResult::absent('i'),
+            CharResult::absent('e'),
+        ] ;
+        "exact matches are counted before misplaced letters"
+    )]
+    #[test_case(
+        "golem",
+        "golem",
+        vec![
+            CharResult::correct('g'),
+            CharResult::correct('o'),
+            CharResult::correct('l'),
+            CharResult::correct('e'),
+            CharResult::correct('m'),
+        ] ;
+        "every letter is Correct when the guess is the word"
+    )]
+    #[test_case(
+        "größe",
+        "grüße",
+        vec![
+            CharResult::correct('g'),
+            CharResult::correct('r'),
+            CharResult::absent('ö'),
+            CharResult::correct('ß'),
+            CharResult::correct('e'),
+        ] ;
+        "letters outside ASCII are scored at their own position"
+    )]
+    #[test_case(
+        "niño",
+        "nin\u{303}o",
+        vec![
+            CharResult::correct('n'),
+            CharResult::correct('i'),
+            CharResult::correct('ñ'),
+            CharResult::correct('o'),
+        ] ;
+        "a letter with a combining accent is the same as its precomposed form"
+    )]
+    fn score_guess_should_score_repeated_letters_like_wordle(
+        guess: &str,
+        word: &str,
+        expected: Vec<CharResult>,
+    ) {
+        let actual = score_guess(
+            &normalized(guess),
+            &char_map_from(
+                &normalized(word),
+            ),
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    proptest! {
+        #[test]
+        fn score_guess_should_never_mark_a_letter_more_often_than_it_occurs_in_the_word(
+            guess in "[a-e]{5}",
+            word in "[a-e]{5}",
+        ) {
+            let attempt = score_guess(&guess, &char_map_from(&word));
+
+            for c in letters_of(&guess) {
+                let marked = attempt
+                    .iter()
+                    .filter(|r| r.letter() == c && *r.result() != GuessResult::Absent)
+                    .count();
+                let occurrences = letters_of(&word).into_iter().filter(|w| *w == c).count();
+
+                prop_assert!(marked <= occurrences);
+            }
+        }
+
+        #[test]
+        fn score_guess_should_mark_exactly_the_matching_positions_as_correct(
+            guess in "[a-e]{5}",
+            word in "[a-e]{5}",
+        ) {
+            let attempt = score_guess(&guess, &char_map_from(&word));
+
+            for ((r, g), w) in attempt.iter().zip(guess.chars()).zip(word.chars()) {
+                prop_assert_eq!(*r.result() == GuessResult::Correct, g == w);
+            }
+        }
+    }
+
+    // letters from several scripts, including one made of two chars
+    const MIXED_SCRIPT_LETTERS:
+        &[&str] = &[
+        "a", "e", "ä", "ö", "ñ",
+        "e\u{301}", "λ", "ж", "한",
+        "글",
+    ];
+
+    fn mixed_script_word(
+    ) -> impl Strategy<Value = String>
+    {
+        vec(
+            select(
+                MIXED_SCRIPT_LETTERS,
+            ),
+            5,
+        )
+        .prop_map(|letters| {
+            letters.concat()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn letter_count_should_count_mixed_script_letters_as_one_each(
+            word in mixed_script_word(),
+        ) {
+            prop_assert_eq!(letter_count(&word), 5);
+        }
+
+        #[test]
+        fn score_guess_should_mark_every_mixed_script_letter_as_correct_when_the_guess_is_the_word(
+            word in mixed_script_word(),
+        ) {
+            let attempt = score_guess(&word, &char_map_from(&word));
+
+            prop_assert_eq!(attempt.len(), 5);
+            prop_assert!(attempt.iter().all(|r| *r.result() == GuessResult::Correct));
+        }
+
+        #[test]
+        fn score_guess_should_align_mixed_script_letters_by_position(
+            guess in mixed_script_word(),
+            word in mixed_script_word(),
+        ) {
+            let attempt = score_guess(&guess, &char_map_from(&word));
+
+            prop_assert_eq!(attempt.len(), 5);
+
+            for ((r, g), w) in attempt.iter().zip(letters_of(&guess)).zip(letters_of(&word)) {
+                prop_assert_eq!(r.letter(), g);
+                prop_assert_eq!(*r.result() == GuessResult::Correct, g == w);
+            }
+        }
+
+        #[test]
+        fn score_guess_should_never_mark_a_mixed_script_letter_more_often_than_it_occurs_in_the_word(
+            guess in mixed_script_word(),
+            word in mixed_script_word(),
+        ) {
+            let attempt = score_guess(&guess, &char_map_from(&word));
+
+            for c in letters_of(&guess) {
+                let marked = attempt
+                    .iter()
+                    .filter(|r| r.letter() == c && *r.result() != GuessResult::Absent)
+                    .count();
+                let occurrences = letters_of(&word).into_iter().filter(|w| *w == c).count();
+
+                prop_assert!(marked <= occurrences);
+            }
+        }
+    }
+
     // fn words_from()
     const GOLEM_IS_INVINCIBLE:
         &[&str] =
@@ -195,6 +595,11 @@ mod tests {
         GOLEM_IS_INVINCIBLE ;
         "multiple-item vec when the input byte array has multiple words and even more multiple newlines."
     )]
+    #[test_case(
+        "Nin\u{303}o",
+        &["niño"] ;
+        "words are lowercased and their combining accents composed."
+    )]
     fn words_from_should_produce_word_vec_from_byte_array(
         input: &str,
         expected: &[&str],
@@ -263,6 +668,105 @@ mod tests {
         );
     }
 
+    // fn load_allowed_guesses()
+    #[test]
+    fn load_allowed_guesses_should_produce_a_non_empty_lowercase_word_list(
+    ) {
+        let allowed =
+            load_allowed_guesses()
+                .unwrap();
+
+        assert!(!allowed.is_empty());
+        assert!(allowed
+            .iter()
+            .all(|w| *w
+                == w.to_lowercase()));
+        assert!(allowed.iter().all(
+            |w| {
+                ALLOWED_GUESS_LENGTHS
+                    .contains(
+                        &letter_count(
+                            w,
+                        ),
+                    )
+            }
+        ));
+    }
+
+    // fn is_allowed_guess()
+    #[test_case("golem", true ; "a word from the allowed guesses file is allowed")]
+    #[test_case("serverless", true ; "a word from the words file is allowed")]
+    #[test_case("abcde", false ; "a made-up word is not allowed")]
+    #[test_case("abcdefghijkl", true ; "a word longer than the allowed guesses is let through")]
+    #[test_case("abc", true ; "a word shorter than the allowed guesses is let through")]
+    #[test_case("", false ; "an empty string is not allowed")]
+    fn is_allowed_guess_should_only_accept_dictionary_words(
+        guess: &str,
+        expected: bool,
+    ) {
+        let actual =
+            is_allowed_guess(guess);
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn pick_word()
+    #[test]
+    fn pick_word_should_only_pick_words_within_the_configured_length_range(
+    ) {
+        let config = GameConfig::new(
+            6, 5, 5, false,
+        )
+        .unwrap();
+
+        for _ in 0..20 {
+            let word =
+                pick_word(&config)
+                    .unwrap();
+
+            assert_eq!(word.len(), 5);
+        }
+    }
+
+    #[test]
+    fn pick_word_should_fail_when_no_word_fits_the_configured_length_range(
+    ) {
+        let config = GameConfig::new(
+            6, 30, 32, false,
+        )
+        .unwrap();
+
+        let actual = pick_word(&config);
+        let expected =
+            AppError::NoWords;
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn word_id_of(), fn word_by_id()
+    #[test]
+    fn word_by_id_should_find_the_word_word_id_of_was_given(
+    ) {
+        let id =
+            word_id_of("serverless")
+                .unwrap();
+
+        assert_eq!(
+            word_by_id(id),
+            Some("serverless")
+        );
+        assert_eq!(
+            word_id_of("dummy"),
+            None
+        );
+        assert_eq!(
+            word_by_id(usize::MAX),
+            None
+        );
+    }
+
     // fn random_number()
     proptest! {
         #[test]
@@ -0,0 +1,411 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    clock::Gmt,
+    core::words_for,
+    game_config::GameConfig,
+};
+use chrono::NaiveDate;
+use error_stack::bail;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+pub const EPOCH_ENV_VAR: &str =
+    "GOLEM_WORDLE_DAILY_EPOCH";
+
+pub const SEED_ENV_VAR: &str =
+    "GOLEM_WORDLE_DAILY_SEED";
+
+pub const DEFAULT_EPOCH: (
+    i32,
+    u32,
+    u32,
+) = (2023, 9, 1);
+
+pub const DEFAULT_SEED: u64 = 2023;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DailyPuzzle {
+    number: usize,
+    word: &'static str,
+}
+impl DailyPuzzle {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub(crate) fn word(
+        &self,
+    ) -> &'static str {
+        self.word
+    }
+}
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct DailySchedule {
+    epoch: NaiveDate,
+    seed: u64,
+}
+impl DailySchedule {
+    pub fn new(
+        epoch: NaiveDate,
+        seed: u64,
+    ) -> Self {
+        Self { epoch, seed }
+    }
+
+    /// Reads the schedule from the environment, the epoch as
+    /// YYYY-MM-DD. A value that is unset or invalid falls back to its
+    /// default.
+    pub fn from_env() -> Self {
+        Self::from_vars(
+            env::var(EPOCH_ENV_VAR)
+                .ok(),
+            env::var(SEED_ENV_VAR).ok(),
+        )
+    }
+
+    fn from_vars(
+        epoch: Option<String>,
+        seed: Option<String>,
+    ) -> Self {
+        let default = Self::default();
+
+        Self::new(
+            epoch
+                .and_then(|v| {
+                    v.trim()
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(
+                    default.epoch,
+                ),
+            seed.and_then(|v| {
+                v.trim().parse().ok()
+            })
+            .unwrap_or(default.seed),
+        )
+    }
+
+    pub fn epoch(&self) -> NaiveDate {
+        self.epoch
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn puzzle_number(
+        &self,
+        today: NaiveDate,
+    ) -> AppResult<usize> {
+        let days = (today - self.epoch)
+            .num_days();
+
+        match usize::try_from(days) {
+            Ok(days) => Ok(days + 1),
+            Err(_) => bail!(
+                AppError::DailyPuzzleNotStarted(
+                    self.epoch.to_string()
+                )
+            ),
+        }
+    }
+
+    pub fn puzzle_for(
+        &self,
+        now: Gmt,
+        config: &GameConfig,
+    ) -> AppResult<DailyPuzzle> {
+        let number = self
+            .puzzle_number(
+                now.date_naive(),
+            )?;
+        let candidates =
+            words_for(config)?;
+
+        let mixed = split_mix(
+            self.seed.wrapping_add(
+                number as u64,
+            ),
+        );
+        let index = usize::try_from(
+            mixed
+                % candidates.len()
+                    as u64,
+        )
+        .unwrap_or_default();
+
+        match candidates.get(index) {
+            Some(word) => {
+                Ok(DailyPuzzle {
+                    number,
+                    word,
+                })
+            }
+            None => {
+                bail!(AppError::NoWords)
+            }
+        }
+    }
+}
+
+impl Default for DailySchedule {
+    #[allow(clippy::unwrap_used)]
+    fn default() -> Self {
+        let (year, month, day) =
+            DEFAULT_EPOCH;
+
+        Self::new(
+            NaiveDate::from_ymd_opt(
+                year, month, day,
+            )
+            .unwrap(),
+            DEFAULT_SEED,
+        )
+    }
+}
+
+/// The step between two states of SplitMix64.
+pub(crate) const GOLDEN_GAMMA: u64 =
+    0x9E37_79B9_7F4A_7C15;
+
+// SplitMix64: a tiny, well-known mixer whose output never changes
+// across platforms or crate versions, unlike `rand`'s StdRng.
+pub(crate) fn split_mix(
+    seed: u64,
+) -> u64 {
+    let mut z =
+        seed.wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(
+        0xBF58_476D_1CE4_E5B9,
+    );
+    z = (z ^ (z >> 27)).wrapping_mul(
+        0x94D0_49BB_1331_11EB,
+    );
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::{
+            tests::TestClock, Clock,
+        },
+    };
+    use chrono::Duration;
+    use pretty_assertions::{
+        assert_eq, assert_ne,
+    };
+    use test_case::test_case;
+
+    fn schedule() -> DailySchedule {
+        DailySchedule::new(
+            NaiveDate::from_ymd_opt(
+                2023, 9, 1,
+            )
+            .unwrap(),
+            DEFAULT_SEED,
+        )
+    }
+
+    // fn from_vars()
+    #[test_case(
+        Some(" 2024-01-01 "), Some("7"),
+        (2024, 1, 1), 7 ;
+        "the epoch and the seed are read from their variables"
+    )]
+    #[test_case(
+        Some("tomorrow"), Some("-1"),
+        DEFAULT_EPOCH, DEFAULT_SEED ;
+        "invalid values fall back to their defaults"
+    )]
+    #[test_case(
+        None, None,
+        DEFAULT_EPOCH, DEFAULT_SEED ;
+        "the defaults are used when nothing is set"
+    )]
+    fn from_vars_should_read_the_schedule(
+        epoch: Option<&str>,
+        seed: Option<&str>,
+        (year, month, day): (
+            i32,
+            u32,
+            u32,
+        ),
+        expected_seed: u64,
+    ) {
+        let actual =
+            DailySchedule::from_vars(
+                epoch.map(
+                    str::to_string,
+                ),
+                seed.map(
+                    str::to_string,
+                ),
+            );
+        let expected =
+            DailySchedule::new(
+                NaiveDate::from_ymd_opt(
+                    year, month, day,
+                )
+                .unwrap(),
+                expected_seed,
+            );
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn puzzle_number()
+    #[test_case(2023, 9, 1, 1 ; "the epoch is puzzle #1")]
+    #[test_case(2023, 9, 2, 2 ; "the day after the epoch is puzzle #2")]
+    #[test_case(2024, 9, 1, 367 ; "a year later counts the leap day")]
+    fn puzzle_number_should_count_days_since_the_epoch(
+        year: i32,
+        month: u32,
+        day: u32,
+        expected: usize,
+    ) {
+        let today =
+            NaiveDate::from_ymd_opt(
+                year, month, day,
+            )
+            .unwrap();
+
+        let actual = schedule()
+            .puzzle_number(today)
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn puzzle_number_should_fail_before_the_epoch(
+    ) {
+        let today =
+            NaiveDate::from_ymd_opt(
+                2023, 8, 31,
+            )
+            .unwrap();
+
+        let actual = schedule()
+            .puzzle_number(today);
+        let expected = AppError::DailyPuzzleNotStarted(
+            "2023-09-01".to_string(),
+        );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn puzzle_for()
+    #[test]
+    fn puzzle_for_should_give_the_same_word_all_day_long(
+    ) {
+        let clock = TestClock::init(
+            2023, 10, 5, 0, 1,
+        );
+        let config =
+            GameConfig::default();
+
+        let morning = schedule()
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )
+            .unwrap();
+
+        clock.advance(Duration::hours(
+            23,
+        ));
+
+        let evening = schedule()
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )
+            .unwrap();
+
+        assert_eq!(morning, evening);
+        assert_eq!(
+            morning.number(),
+            35
+        );
+    }
+
+    #[test]
+    fn puzzle_for_should_move_on_to_the_next_puzzle_the_next_day(
+    ) {
+        let clock = TestClock::init(
+            2023, 10, 5, 12, 0,
+        );
+        let config =
+            GameConfig::default();
+
+        let today = schedule()
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )
+            .unwrap();
+
+        clock
+            .advance(Duration::days(1));
+
+        let tomorrow = schedule()
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )
+            .unwrap();
+
+        assert_eq!(
+            tomorrow.number(),
+            today.number() + 1
+        );
+    }
+
+    #[test]
+    fn puzzle_for_should_depend_on_the_seed(
+    ) {
+        let clock = TestClock::init(
+            2023, 10, 5, 12, 0,
+        );
+        let config =
+            GameConfig::default();
+
+        let words: Vec<_> = (0..10)
+            .map(|seed| {
+                DailySchedule::new(
+                    schedule().epoch(),
+                    seed,
+                )
+                .puzzle_for(
+                    clock.now(),
+                    &config,
+                )
+                .unwrap()
+                .word()
+            })
+            .collect();
+
+        assert_ne!(
+            words
+                .iter()
+                .filter(
+                    |w| **w == words[0]
+                )
+                .count(),
+            words.len()
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    hint::DEFAULT_HINT_BUDGET,
+};
+use chrono::Duration;
+use error_stack::bail;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+pub const DEFAULT_MAX_ATTEMPTS: usize =
+    6;
+
+pub const DEFAULT_MIN_WORD_LENGTH:
+    usize = 1;
+
+pub const DEFAULT_MAX_WORD_LENGTH:
+    usize = 32;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    /// The word is not chosen up front: every guess is answered so
+    /// that as many words as possible still fit.
+    Adversarial,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct GameConfig {
+    max_attempts: usize,
+    word_length_range:
+        RangeInclusive<usize>,
+    hard_mode: bool,
+    #[serde(
+        default = "default_hint_budget"
+    )]
+    hint_budget: usize,
+    // in whole seconds
+    #[serde(default)]
+    game_time_limit: Option<i64>,
+    #[serde(default)]
+    guess_time_limit: Option<i64>,
+    #[serde(default)]
+    mode: GameMode,
+}
+
+fn default_hint_budget() -> usize {
+    DEFAULT_HINT_BUDGET
+}
+
+impl GameConfig {
+    pub fn new(
+        max_attempts: usize,
+        min_word_length: usize,
+        max_word_length: usize,
+        hard_mode: bool,
+    ) -> AppResult<Self> {
+        if max_attempts == 0 {
+            bail!(AppError::InvalidGameConfig(
+                "max attempts must be at least 1".to_string()
+            ))
+        }
+
+        if min_word_length == 0 {
+            bail!(AppError::InvalidGameConfig(
+                "min word length must be at least 1".to_string()
+            ))
+        }
+
+        if min_word_length
+            > max_word_length
+        {
+            bail!(AppError::InvalidGameConfig(
+                format!(
+                    "min word length ({}) must not exceed max word length ({})",
+                    min_word_length,
+                    max_word_length
+                )
+            ))
+        }
+
+        Ok(Self {
+            max_attempts,
+            word_length_range:
+                min_word_length
+                    ..=max_word_length,
+            hard_mode,
+            hint_budget:
+                DEFAULT_HINT_BUDGET,
+            game_time_limit: None,
+            guess_time_limit: None,
+            mode: GameMode::Classic,
+        })
+    }
+
+    /// Keeps to words of exactly this many letters.
+    pub(crate) fn with_word_length(
+        self,
+        length: usize,
+    ) -> Self {
+        Self {
+            word_length_range: length
+                ..=length,
+            ..self
+        }
+    }
+
+    pub fn with_mode(
+        self,
+        mode: GameMode,
+    ) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn with_hint_budget(
+        self,
+        hint_budget: usize,
+    ) -> Self {
+        Self {
+            hint_budget,
+            ..self
+        }
+    }
+
+    /// Makes the game timed: it ends once `per_game` has passed since
+    /// it started, or `per_guess` since the last guess.
+    pub fn with_time_limits(
+        self,
+        per_game: Option<Duration>,
+        per_guess: Option<Duration>,
+    ) -> AppResult<Self> {
+        if [per_game, per_guess]
+            .iter()
+            .flatten()
+            .any(|limit| {
+                limit.num_seconds() < 1
+            })
+        {
+            bail!(AppError::InvalidGameConfig(
+                "time limits must be at least 1 second".to_string()
+            ))
+        }
+
+        Ok(Self {
+            game_time_limit: per_game
+                .map(|l| {
+                    l.num_seconds()
+                }),
+            guess_time_limit: per_guess
+                .map(|l| {
+                    l.num_seconds()
+                }),
+            ..self
+        })
+    }
+
+    /// The config once more through the checks of `new` and
+    /// `with_time_limits`, for a config that was decoded rather than
+    /// built.
+    pub(crate) fn checked(
+        &self,
+    ) -> AppResult<Self> {
+        Ok(Self::new(
+            self.max_attempts,
+            *self
+                .word_length_range
+                .start(),
+            *self
+                .word_length_range
+                .end(),
+            self.hard_mode,
+        )?
+        .with_time_limits(
+            self.game_time_limit(),
+            self.guess_time_limit(),
+        )?
+        .with_hint_budget(
+            self.hint_budget,
+        )
+        .with_mode(self.mode))
+    }
+
+    pub fn max_attempts(
+        &self,
+    ) -> usize {
+        self.max_attempts
+    }
+
+    pub fn word_length_range(
+        &self,
+    ) -> &RangeInclusive<usize> {
+        &self.word_length_range
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Number of hints a player may ask for in a game.
+    pub fn hint_budget(&self) -> usize {
+        self.hint_budget
+    }
+
+    pub fn game_time_limit(
+        &self,
+    ) -> Option<Duration> {
+        self.game_time_limit
+            .map(Duration::seconds)
+    }
+
+    pub fn guess_time_limit(
+        &self,
+    ) -> Option<Duration> {
+        self.guess_time_limit
+            .map(Duration::seconds)
+    }
+
+    pub fn is_timed(&self) -> bool {
+        self.game_time_limit.is_some()
+            || self
+                .guess_time_limit
+                .is_some()
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn is_adversarial(
+        &self,
+    ) -> bool {
+        self.mode
+            == GameMode::Adversarial
+    }
+
+    pub fn allows_word_length(
+        &self,
+        length: usize,
+    ) -> bool {
+        self.word_length_range
+            .contains(&length)
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts:
+                DEFAULT_MAX_ATTEMPTS,
+            word_length_range:
+                DEFAULT_MIN_WORD_LENGTH
+                    ..=DEFAULT_MAX_WORD_LENGTH,
+            hard_mode: false,
+            hint_budget:
+                DEFAULT_HINT_BUDGET,
+            game_time_limit: None,
+            guess_time_limit: None,
+            mode: GameMode::Classic,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_app_error;
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    // fn new()
+    #[test]
+    fn new_should_produce_a_game_config_when_settings_are_valid(
+    ) {
+        let config = GameConfig::new(
+            3, 4, 8, true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.max_attempts(),
+            3
+        );
+        assert_eq!(
+            *config.word_length_range(),
+            4..=8
+        );
+        assert!(config.hard_mode());
+    }
+
+    #[test_case(
+        0, 4, 8,
+        "max attempts must be at least 1" ;
+        "AppError::InvalidGameConfig when max attempts is zero."
+    )]
+    #[test_case(
+        6, 0, 8,
+        "min word length must be at least 1" ;
+        "AppError::InvalidGameConfig when min word length is zero."
+    )]
+    #[test_case(
+        6, 9, 8,
+        "min word length (9) must not exceed max word length (8)" ;
+        "AppError::InvalidGameConfig when the word length range is empty."
+    )]
+    fn new_should_fail_when_settings_are_invalid(
+        max_attempts: usize,
+        min_word_length: usize,
+        max_word_length: usize,
+        reason: &str,
+    ) {
+        let actual = GameConfig::new(
+            max_attempts,
+            min_word_length,
+            max_word_length,
+            false,
+        );
+        let expected =
+            AppError::InvalidGameConfig(
+                reason.to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn with_time_limits()
+    #[test]
+    fn with_time_limits_should_make_the_game_timed(
+    ) {
+        let config =
+            GameConfig::default()
+                .with_time_limits(
+                    Some(
+                        Duration::minutes(
+                            3,
+                        ),
+                    ),
+                    None,
+                )
+                .unwrap();
+
+        assert!(config.is_timed());
+        assert_eq!(
+            config.game_time_limit(),
+            Some(Duration::minutes(3))
+        );
+        assert_eq!(
+            config.guess_time_limit(),
+            None
+        );
+        assert!(!GameConfig::default()
+            .is_timed());
+    }
+
+    #[test]
+    fn with_time_limits_should_fail_on_a_limit_under_a_second(
+    ) {
+        let actual =
+            GameConfig::default()
+                .with_time_limits(
+                    None,
+                    Some(
+                        Duration::zero(
+                        ),
+                    ),
+                );
+        let expected =
+            AppError::InvalidGameConfig(
+                "time limits must be at least 1 second"
+                    .to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn allows_word_length()
+    #[test_case(3, false ; "a length below the range is not allowed")]
+    #[test_case(4, true ; "the lower bound is allowed")]
+    #[test_case(8, true ; "the upper bound is allowed")]
+    #[test_case(9, false ; "a length above the range is not allowed")]
+    fn allows_word_length_should_check_the_configured_range(
+        length: usize,
+        expected: bool,
+    ) {
+        let config = GameConfig::new(
+            6, 4, 8, false,
+        )
+        .unwrap();
+
+        let actual = config
+            .allows_word_length(length);
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn default()
+    #[test]
+    fn default_should_give_six_attempts_regardless_of_word_length(
+    ) {
+        let config =
+            GameConfig::default();
+
+        assert_eq!(
+            config.max_attempts(),
+            DEFAULT_MAX_ATTEMPTS
+        );
+        assert!(config
+            .allows_word_length(4));
+        assert!(config
+            .allows_word_length(11));
+        assert!(!config.hard_mode());
+    }
+}
@@ -0,0 +1,783 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    char_result::CharResult,
+    clock::{Gmt, ManualClock},
+    game_config::GameConfig,
+    game_state::GameState,
+    hint::GivenHint,
+    session_state::SessionState,
+};
+use chrono::Duration;
+use error_stack::bail;
+use std::collections::VecDeque;
+
+pub const DEFAULT_HISTORY_CAPACITY:
+    usize = 100;
+
+#[derive(
+    Clone, Copy, Debug, PartialEq,
+)]
+pub enum GameOutcome {
+    Won,
+    Lost,
+    TimedOut,
+}
+impl GameOutcome {
+    pub(crate) fn of(
+        game_state: &GameState,
+    ) -> Self {
+        if game_state.is_won() {
+            Self::Won
+        } else if game_state
+            .is_timed_out()
+        {
+            Self::TimedOut
+        } else {
+            Self::Lost
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    id: u64,
+    word: String,
+    attempts: Vec<Vec<CharResult>>,
+    attempted_at: Vec<Gmt>,
+    started_at: Gmt,
+    ended_at: Gmt,
+    outcome: GameOutcome,
+    hints: Vec<GivenHint>,
+    late_guess: Option<(String, Gmt)>,
+    config: GameConfig,
+    puzzle_number: Option<usize>,
+}
+impl GameRecord {
+    fn of(
+        id: u64,
+        game_state: &GameState,
+    ) -> Self {
+        Self {
+            id,
+            word: game_state
+                .word()
+                .to_string(),
+            attempts: game_state
+                .attempts()
+                .to_vec(),
+            attempted_at: game_state
+                .attempted_at(),
+            started_at: game_state
+                .started_at(),
+            ended_at: game_state
+                .last_update(),
+            outcome: GameOutcome::of(
+                game_state,
+            ),
+            hints: game_state
+                .hints()
+                .to_vec(),
+            late_guess: game_state
+                .late_guess()
+                .map(|(guess, time)| {
+                    (
+                        guess.to_string(),
+                        time,
+                    )
+                }),
+            config: game_state
+                .config()
+                .clone(),
+            puzzle_number: game_state
+                .puzzle_number(),
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn attempts(
+        &self,
+    ) -> &[Vec<CharResult>] {
+        &self.attempts
+    }
+
+    pub fn guesses(
+        &self,
+    ) -> Vec<String> {
+        self.attempts
+            .iter()
+            .map(|attempt| {
+                attempt
+                    .iter()
+                    .map(|r| r.letter())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn started_at(&self) -> Gmt {
+        self.started_at
+    }
+
+    pub fn ended_at(&self) -> Gmt {
+        self.ended_at
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.ended_at - self.started_at
+    }
+
+    pub fn outcome(
+        &self,
+    ) -> GameOutcome {
+        self.outcome
+    }
+
+    pub fn hints(
+        &self,
+    ) -> &[GivenHint] {
+        &self.hints
+    }
+
+    pub fn config(
+        &self,
+    ) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn puzzle_number(
+        &self,
+    ) -> Option<usize> {
+        self.puzzle_number
+    }
+
+    pub fn describe(&self) -> String {
+        let outcome = match self.outcome
+        {
+            GameOutcome::Won => {
+                format!(
+                    "won in {}/{}",
+                    self.attempts.len(),
+                    self.config
+                        .max_attempts()
+                )
+            }
+            GameOutcome::Lost => {
+                "lost".to_string()
+            }
+            GameOutcome::TimedOut => {
+                "ran out of time"
+                    .to_string()
+            }
+        };
+        let outcome = match self
+            .hints
+            .len()
+        {
+            0 => outcome,
+            1 => format!(
+                "{outcome} with 1 hint"
+            ),
+            n => format!(
+                "{outcome} with {n} hints"
+            ),
+        };
+
+        format!(
+            "Game {}: '{}', {}, played from {} to {}.",
+            self.id,
+            self.word,
+            outcome,
+            self.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.ended_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        )
+    }
+
+    /// Plays the guesses again at the times they were first made, so
+    /// that every step answers exactly what the player was shown.
+    pub fn replay(
+        &self,
+    ) -> Vec<ReplayStep> {
+        let clock = ManualClock::new(
+            self.started_at,
+        );
+        let mut game_state =
+            GameState::of(
+                &self.word,
+                self.config.clone(),
+                &clock,
+            );
+
+        if let Some(number) =
+            self.puzzle_number
+        {
+            game_state = game_state
+                .with_puzzle_number(
+                    number,
+                );
+        }
+
+        let mut steps = vec![];
+
+        let mut hints = self
+            .hints
+            .iter()
+            .peekable();
+
+        for (i, (guess, time)) in self
+            .guesses()
+            .into_iter()
+            .zip(&self.attempted_at)
+            .enumerate()
+        {
+            while let Some(hint) = hints
+                .next_if(|h| {
+                    h.after_attempts()
+                        <= i
+                })
+            {
+                game_state.add_hint(
+                    hint.clone(),
+                );
+            }
+
+            clock.set(*time);
+
+            let session_state =
+                SessionState::after_guess(
+                    &guess,
+                    &mut game_state,
+                );
+
+            steps.push(ReplayStep {
+                guess,
+                session_state,
+            });
+        }
+
+        // the guess that came in too late was never played, but it is
+        // what told the player their time was up
+        if let Some((guess, time)) =
+            &self.late_guess
+        {
+            clock.set(*time);
+
+            if let Ok(session_state) =
+                SessionState::determined_by(
+                    guess,
+                    &mut game_state,
+                )
+            {
+                steps.push(ReplayStep {
+                    guess: guess.clone(),
+                    session_state,
+                });
+            }
+        }
+
+        steps
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReplayStep {
+    guess: String,
+    session_state: SessionState,
+}
+impl ReplayStep {
+    pub fn guess(&self) -> &str {
+        &self.guess
+    }
+
+    pub fn session_state(
+        &self,
+    ) -> &SessionState {
+        &self.session_state
+    }
+}
+
+/// The most recent finished games, oldest first.
+pub struct GameHistory {
+    records: VecDeque<GameRecord>,
+    capacity: usize,
+}
+impl GameHistory {
+    pub(crate) const fn new(
+        capacity: usize,
+    ) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Adds a finished game under the id it was played as, dropping
+    /// the oldest one when the history is full. Games still in
+    /// progress are ignored.
+    pub fn record(
+        &mut self,
+        id: u64,
+        game_state: &GameState,
+    ) -> Option<u64> {
+        if !game_state.is_finished()
+            || self.capacity == 0
+        {
+            return None;
+        }
+
+        if self.records.len()
+            == self.capacity
+        {
+            self.records.pop_front();
+        }
+
+        self.records.push_back(
+            GameRecord::of(
+                id, game_state,
+            ),
+        );
+
+        Some(id)
+    }
+
+    pub fn games(
+        &self,
+    ) -> impl Iterator<Item = &GameRecord>
+    {
+        self.records.iter()
+    }
+
+    pub fn last(
+        &self,
+    ) -> AppResult<&GameRecord> {
+        match self.records.back() {
+            Some(record) => Ok(record),
+            None => bail!(
+                AppError::NoFinishedGame
+            ),
+        }
+    }
+
+    pub fn game(
+        &self,
+        id: u64,
+    ) -> AppResult<&GameRecord> {
+        match self
+            .records
+            .iter()
+            .find(|r| r.id == id)
+        {
+            Some(record) => Ok(record),
+            None => bail!(
+                AppError::GameNotFound(
+                    id
+                )
+            ),
+        }
+    }
+
+    pub fn replay(
+        &self,
+        id: u64,
+    ) -> AppResult<Vec<ReplayStep>>
+    {
+        self.game(id)
+            .map(GameRecord::replay)
+    }
+}
+
+impl Default for GameHistory {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_HISTORY_CAPACITY,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::tests::TestClock,
+    };
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+
+    const WORD: &str = "golem";
+
+    fn played_game<'a>(
+        clock: &'a TestClock,
+        guesses: &[(&str, i64)],
+    ) -> GameState<'a> {
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                clock,
+            );
+
+        for (guess, minutes) in guesses
+        {
+            clock.advance(
+                Duration::minutes(
+                    *minutes,
+                ),
+            );
+
+            SessionState::determined_by(
+                guess,
+                &mut game_state,
+            )
+            .unwrap();
+        }
+
+        game_state
+    }
+
+    // fn record()
+    #[test]
+    fn record_should_keep_only_the_most_recent_games(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut history =
+            GameHistory::new(2);
+
+        for id in 1..=3 {
+            history.record(
+                id,
+                &played_game(
+                    &clock,
+                    &[("golem", 1)],
+                ),
+            );
+        }
+
+        let ids: Vec<_> = history
+            .games()
+            .map(|r| r.id())
+            .collect();
+
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn record_should_ignore_a_game_in_progress(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut history =
+            GameHistory::default();
+
+        let actual = history.record(
+            1,
+            &played_game(
+                &clock,
+                &[("spice", 1)],
+            ),
+        );
+
+        assert_eq!(actual, None);
+        assert_eq!(
+            history.games().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn record_should_keep_the_guesses_times_and_outcome(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 24, 9, 30,
+        );
+        let mut history =
+            GameHistory::default();
+
+        let id = history
+            .record(
+                1,
+                &played_game(
+                    &clock,
+                    &[
+                        ("spice", 1),
+                        ("golem", 2),
+                    ],
+                ),
+            )
+            .unwrap();
+        let record =
+            history.game(id).unwrap();
+
+        assert_eq!(record.word(), WORD);
+        assert_eq!(
+            record.guesses(),
+            vec!["spice", "golem"]
+        );
+        assert_eq!(
+            record.outcome(),
+            GameOutcome::Won
+        );
+        assert_eq!(
+            record.ended_at()
+                - record.started_at(),
+            Duration::minutes(3)
+        );
+        assert_eq!(
+            record.describe(),
+            "Game 1: 'golem', won in 2/6, played from 2023-12-24 09:30:00 UTC to 2023-12-24 09:33:00 UTC."
+        );
+    }
+
+    #[test]
+    fn record_should_end_a_timed_out_game_at_its_deadline(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 24, 9, 30,
+        );
+        let mut history =
+            GameHistory::default();
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default()
+                    .with_time_limits(
+                        Some(
+                            Duration::minutes(
+                                2,
+                            ),
+                        ),
+                        None,
+                    )
+                    .unwrap(),
+                &clock,
+            );
+
+        clock.advance(
+            Duration::minutes(5),
+        );
+        SessionState::determined_by(
+            WORD,
+            &mut game_state,
+        )
+        .unwrap();
+
+        let id = history
+            .record(1, &game_state)
+            .unwrap();
+        let record =
+            history.game(id).unwrap();
+
+        assert_eq!(
+            record.outcome(),
+            GameOutcome::TimedOut
+        );
+        assert_eq!(
+            record.elapsed(),
+            Duration::minutes(2)
+        );
+        assert_eq!(
+            record.describe(),
+            "Game 1: 'golem', ran out of time, played from 2023-12-24 09:30:00 UTC to 2023-12-24 09:32:00 UTC."
+        );
+    }
+
+    // fn replay()
+    #[test]
+    fn replay_should_reproduce_what_the_player_saw(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 24, 9, 30,
+        );
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                &clock,
+            );
+        let mut seen = vec![];
+
+        // the long break makes the game recap itself
+        for (guess, minutes) in [
+            ("spice", 1),
+            ("board", 30),
+            ("golem", 1),
+        ] {
+            clock.advance(
+                Duration::minutes(
+                    minutes,
+                ),
+            );
+            seen.push(
+                SessionState::determined_by(
+                    guess,
+                    &mut game_state,
+                )
+                .unwrap(),
+            );
+        }
+
+        let mut history =
+            GameHistory::default();
+        let id = history
+            .record(1, &game_state)
+            .unwrap();
+
+        let actual: Vec<_> = history
+            .replay(id)
+            .unwrap()
+            .into_iter()
+            .map(|step| {
+                step.session_state
+            })
+            .collect();
+
+        assert_eq!(actual, seen);
+    }
+
+    #[test]
+    fn replay_should_take_the_hints_at_the_same_point_of_the_game(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                &clock,
+            );
+        let mut seen = vec![];
+
+        for guess in ["spice", "golem"]
+        {
+            game_state.hint().unwrap();
+            seen.push(
+                SessionState::determined_by(
+                    guess,
+                    &mut game_state,
+                )
+                .unwrap(),
+            );
+        }
+
+        let mut history =
+            GameHistory::default();
+        let id = history
+            .record(1, &game_state)
+            .unwrap();
+
+        let actual: Vec<_> = history
+            .replay(id)
+            .unwrap()
+            .into_iter()
+            .map(|step| {
+                step.session_state
+            })
+            .collect();
+
+        assert_eq!(actual, seen);
+        assert_eq!(
+            history
+                .game(id)
+                .unwrap()
+                .hints()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn replay_should_end_with_the_guess_that_ran_out_of_time(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 24, 9, 30,
+        );
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default()
+                    .with_time_limits(
+                        Some(
+                            Duration::minutes(
+                                5,
+                            ),
+                        ),
+                        None,
+                    )
+                    .unwrap(),
+                &clock,
+            );
+        let mut seen = vec![];
+
+        for (guess, minutes) in [
+            ("spice", 1),
+            ("board", 10),
+        ] {
+            clock.advance(
+                Duration::minutes(
+                    minutes,
+                ),
+            );
+            seen.push(
+                SessionState::determined_by(
+                    guess,
+                    &mut game_state,
+                )
+                .unwrap(),
+            );
+        }
+
+        let mut history =
+            GameHistory::default();
+        let id = history
+            .record(1, &game_state)
+            .unwrap();
+
+        let actual: Vec<_> = history
+            .replay(id)
+            .unwrap()
+            .into_iter()
+            .map(|step| {
+                (
+                    step.guess,
+                    step.session_state,
+                )
+            })
+            .collect();
+        let expected: Vec<_> =
+            ["spice", "board"]
+                .into_iter()
+                .map(str::to_string)
+                .zip(seen)
+                .collect();
+
+        assert_eq!(actual, expected);
+        assert!(matches!(
+            actual[1].1,
+            SessionState::TimedOut(_)
+        ));
+    }
+
+    #[test]
+    fn replay_should_fail_when_the_game_is_not_in_the_history(
+    ) {
+        let history =
+            GameHistory::default();
+
+        let actual = history
+            .replay(7)
+            .map(|_| ());
+        let expected =
+            AppError::GameNotFound(7);
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+}
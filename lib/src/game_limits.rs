@@ -0,0 +1,195 @@
+use crate::app_error::{
+    AppError, AppResult,
+};
+use chrono::Duration;
+use error_stack::bail;
+use std::env;
+
+pub const MAX_GAMES_ENV_VAR: &str =
+    "GOLEM_WORDLE_MAX_GAMES";
+
+pub const STALE_GAME_MINUTES_ENV_VAR:
+    &str =
+    "GOLEM_WORDLE_STALE_GAME_MINUTES";
+
+pub const DEFAULT_MAX_GAMES: usize = 10;
+
+/// A week.
+pub const DEFAULT_STALE_GAME_MINUTES:
+    i64 = 7 * 24 * 60;
+
+/// How many games a worker runs at once, and how long a game may sit
+/// untouched before it is dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameLimits {
+    max_games: usize,
+    stale_after: Duration,
+}
+impl GameLimits {
+    pub fn new(
+        max_games: usize,
+        stale_after: Duration,
+    ) -> AppResult<Self> {
+        if max_games == 0 {
+            bail!(AppError::InvalidGameLimits(
+                "max games must be at least 1".to_string()
+            ))
+        }
+
+        if stale_after.num_minutes() < 1
+        {
+            bail!(AppError::InvalidGameLimits(
+                "games must be kept for at least 1 minute".to_string()
+            ))
+        }
+
+        Ok(Self {
+            max_games,
+            stale_after,
+        })
+    }
+
+    /// Reads the limits from the environment. A value that is unset
+    /// or invalid falls back to its default.
+    pub fn from_env() -> Self {
+        Self::from_vars(
+            env::var(MAX_GAMES_ENV_VAR)
+                .ok(),
+            env::var(
+                STALE_GAME_MINUTES_ENV_VAR,
+            )
+            .ok(),
+        )
+    }
+
+    fn from_vars(
+        max_games: Option<String>,
+        stale_game_minutes: Option<
+            String,
+        >,
+    ) -> Self {
+        let default = Self::default();
+
+        let max_games = max_games
+            .and_then(|v| {
+                v.trim().parse().ok()
+            })
+            .unwrap_or(
+                default.max_games,
+            );
+        let stale_after =
+            stale_game_minutes
+                .and_then(|v| {
+                    v.trim()
+                        .parse()
+                        .ok()
+                })
+                .map(Duration::minutes)
+                .unwrap_or(
+                    default.stale_after,
+                );
+
+        Self::new(
+            max_games,
+            stale_after,
+        )
+        .unwrap_or(default)
+    }
+
+    pub fn max_games(&self) -> usize {
+        self.max_games
+    }
+
+    pub fn stale_after(
+        &self,
+    ) -> Duration {
+        self.stale_after
+    }
+}
+
+impl Default for GameLimits {
+    fn default() -> Self {
+        Self {
+            max_games:
+                DEFAULT_MAX_GAMES,
+            stale_after:
+                Duration::minutes(
+                    DEFAULT_STALE_GAME_MINUTES,
+                ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_app_error;
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    // fn new()
+    #[test_case(
+        0, 60,
+        "max games must be at least 1" ;
+        "AppError::InvalidGameLimits when max games is zero."
+    )]
+    #[test_case(
+        3, 0,
+        "games must be kept for at least 1 minute" ;
+        "AppError::InvalidGameLimits when games would never be kept."
+    )]
+    fn new_should_fail_when_limits_are_invalid(
+        max_games: usize,
+        stale_game_minutes: i64,
+        reason: &str,
+    ) {
+        let actual = GameLimits::new(
+            max_games,
+            Duration::minutes(
+                stale_game_minutes,
+            ),
+        );
+        let expected =
+            AppError::InvalidGameLimits(
+                reason.to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn from_vars()
+    #[test]
+    fn from_vars_should_read_each_limit_and_fall_back_on_bad_values(
+    ) {
+        let actual =
+            GameLimits::from_vars(
+                Some(" 3 ".to_string()),
+                Some(
+                    "soon".to_string(),
+                ),
+            );
+
+        assert_eq!(
+            actual.max_games(),
+            3
+        );
+        assert_eq!(
+            actual.stale_after(),
+            GameLimits::default()
+                .stale_after()
+        );
+    }
+
+    #[test]
+    fn from_vars_should_give_the_defaults_when_nothing_is_set(
+    ) {
+        assert_eq!(
+            GameLimits::from_vars(
+                None, None
+            ),
+            GameLimits::default()
+        );
+    }
+}
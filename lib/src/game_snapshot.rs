@@ -0,0 +1,458 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    char_result::CharResult,
+    clock::{Clock, Gmt},
+    core::{
+        letter_count, word_by_id,
+        word_id_of,
+    },
+    game_config::GameConfig,
+    game_state::GameState,
+    hint::GivenHint,
+};
+use error_stack::{bail, ResultExt};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct GameSnapshot {
+    word_id: usize,
+    word_length: usize,
+    attempts: Vec<Vec<CharResult>>,
+    #[serde(default)]
+    attempted_at: Vec<Gmt>,
+    started_at: Gmt,
+    last_update: Gmt,
+    config: GameConfig,
+    #[serde(default)]
+    puzzle_number: Option<usize>,
+    #[serde(default)]
+    hints: Vec<GivenHint>,
+    #[serde(default)]
+    timed_out: bool,
+}
+impl GameSnapshot {
+    pub fn of(
+        game_state: &GameState,
+    ) -> AppResult<Self> {
+        let Some(word_id) = word_id_of(
+            game_state.word(),
+        ) else {
+            bail!(AppError::InvalidSnapshot(
+                "the word is not in the word list".to_string()
+            ))
+        };
+
+        Ok(Self {
+            word_id,
+            word_length: game_state
+                .word_length(),
+            attempts: game_state
+                .attempts()
+                .to_vec(),
+            attempted_at: game_state
+                .attempted_at(),
+            started_at: game_state
+                .started_at(),
+            last_update: game_state
+                .last_update(),
+            config: game_state
+                .config()
+                .clone(),
+            puzzle_number: game_state
+                .puzzle_number(),
+            hints: game_state
+                .hints()
+                .to_vec(),
+            timed_out: game_state
+                .is_timed_out(),
+        })
+    }
+
+    pub fn restore<'a>(
+        &self,
+        clock: &'a impl Clock,
+    ) -> AppResult<GameState<'a>> {
+        let Some(word) =
+            word_by_id(self.word_id)
+        else {
+            bail!(AppError::InvalidSnapshot(
+                format!("unknown word id {}", self.word_id)
+            ))
+        };
+
+        // a decoded config has not been through the checks of its
+        // constructor
+        let config = self
+            .config
+            .checked()
+            .change_context(
+                AppError::InvalidSnapshot(
+                    "the config is not valid".to_string(),
+                ),
+            )?;
+
+        if letter_count(word)
+            != self.word_length
+        {
+            bail!(AppError::InvalidSnapshot(
+                "the word length does not match the word list".to_string()
+            ))
+        }
+
+        if self.attempts.len()
+            > config.max_attempts()
+        {
+            bail!(AppError::InvalidSnapshot(
+                "more attempts than the config allows".to_string()
+            ))
+        }
+
+        if self.attempts.iter().any(
+            |a| {
+                a.len()
+                    != self.word_length
+            },
+        ) {
+            bail!(AppError::InvalidSnapshot(
+                "an attempt does not match the word length".to_string()
+            ))
+        }
+
+        let game_state =
+            GameState::restored(
+                word,
+                self.attempts.clone(),
+                self.attempted_at
+                    .clone(),
+                self.started_at,
+                self.last_update,
+                config,
+                clock,
+            )
+            .with_hints(
+                self.hints.clone(),
+            )
+            .with_timed_out(
+                self.timed_out,
+            );
+
+        Ok(match self.puzzle_number {
+            Some(number) => game_state
+                .with_puzzle_number(
+                    number,
+                ),
+            None => game_state,
+        })
+    }
+
+    pub fn to_bytes(
+        &self,
+    ) -> AppResult<Vec<u8>> {
+        serde_json::to_vec(self)
+            .change_context(
+            AppError::SnapshotEncode,
+        )
+    }
+
+    pub fn from_bytes(
+        bytes: &[u8],
+    ) -> AppResult<Self> {
+        serde_json::from_slice(bytes)
+            .change_context(
+            AppError::SnapshotDecode,
+        )
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> AppResult<()> {
+        let bytes = self.to_bytes()?;
+
+        fs::write(path, bytes)
+            .change_context(
+                AppError::FileWrite(
+                    path.display()
+                        .to_string(),
+                ),
+            )
+    }
+
+    pub fn load(
+        path: &Path,
+    ) -> AppResult<Self> {
+        let bytes = fs::read(path)
+            .change_context(
+                AppError::FileRead(
+                    path.display()
+                        .to_string(),
+                ),
+            )?;
+
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::{
+            tests::TestClock, RealClock,
+        },
+    };
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+
+    fn played_game_state(
+        clock: &TestClock,
+    ) -> GameState<'_> {
+        let mut game_state =
+            GameState::of(
+                word_by_id(0).unwrap(),
+                GameConfig::new(
+                    4, 1, 32, true,
+                )
+                .unwrap(),
+                clock,
+            )
+            .with_puzzle_number(7);
+
+        clock.advance(
+            Duration::minutes(3),
+        );
+
+        let attempt =
+            game_state.score("xxxxx");
+        game_state.add_attempt(attempt);
+        game_state.hint().unwrap();
+
+        game_state
+    }
+
+    // fn of(), fn restore()
+    #[test]
+    fn restore_should_produce_the_game_state_the_snapshot_was_taken_of(
+    ) {
+        let clock = TestClock::init(
+            2000, 1, 1, 1, 0,
+        );
+        let game_state =
+            played_game_state(&clock);
+
+        let snapshot =
+            GameSnapshot::of(
+                &game_state,
+            )
+            .unwrap();
+        let restored = snapshot
+            .restore(&clock)
+            .unwrap();
+
+        assert_eq!(
+            restored.word(),
+            game_state.word()
+        );
+        assert_eq!(
+            restored.attempts(),
+            game_state.attempts()
+        );
+        assert_eq!(
+            restored.keyboard(),
+            game_state.keyboard()
+        );
+        assert_eq!(
+            restored.hints(),
+            game_state.hints()
+        );
+        assert_eq!(
+            restored.started_at(),
+            game_state.started_at()
+        );
+        assert_eq!(
+            restored.last_update(),
+            game_state.last_update()
+        );
+        assert_eq!(
+            restored.config(),
+            game_state.config()
+        );
+        assert_eq!(
+            restored.puzzle_number(),
+            Some(7)
+        );
+        assert_eq!(
+            GameSnapshot::of(&restored)
+                .unwrap(),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn of_should_fail_when_the_word_is_not_in_the_word_list(
+    ) {
+        let game_state = GameState::of(
+            "dummy",
+            GameConfig::default(),
+            &RealClock,
+        );
+
+        let actual = GameSnapshot::of(
+            &game_state,
+        );
+        let expected =
+            AppError::InvalidSnapshot(
+                "the word is not in the word list".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn restore_should_fail_when_the_word_id_is_unknown(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut snapshot =
+            GameSnapshot::of(
+                &played_game_state(
+                    &clock,
+                ),
+            )
+            .unwrap();
+        snapshot.word_id = usize::MAX;
+
+        let actual = snapshot
+            .restore(&clock)
+            .map(|_| ());
+        let expected =
+            AppError::InvalidSnapshot(
+                format!(
+                    "unknown word id {}",
+                    usize::MAX
+                ),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn restore_should_fail_when_the_decoded_config_is_not_valid(
+    ) {
+        let clock =
+            TestClock::default();
+        let snapshot =
+            GameSnapshot::of(
+                &played_game_state(
+                    &clock,
+                ),
+            )
+            .unwrap();
+        let bytes = String::from_utf8(
+            snapshot
+                .to_bytes()
+                .unwrap(),
+        )
+        .unwrap()
+        .replace(
+            r#""max_attempts":4"#,
+            r#""max_attempts":0"#,
+        );
+
+        let actual =
+            GameSnapshot::from_bytes(
+                bytes.as_bytes(),
+            )
+            .unwrap()
+            .restore(&clock)
+            .map(|_| ());
+        let expected =
+            AppError::InvalidSnapshot(
+                "the config is not valid".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn to_bytes(), fn from_bytes()
+    #[test]
+    fn from_bytes_should_decode_what_to_bytes_encoded(
+    ) {
+        let clock =
+            TestClock::default();
+        let snapshot =
+            GameSnapshot::of(
+                &played_game_state(
+                    &clock,
+                ),
+            )
+            .unwrap();
+
+        let bytes = snapshot
+            .to_bytes()
+            .unwrap();
+        let actual =
+            GameSnapshot::from_bytes(
+                &bytes,
+            )
+            .unwrap();
+
+        assert_eq!(actual, snapshot);
+    }
+
+    #[test]
+    fn from_bytes_should_fail_when_bytes_are_not_a_snapshot(
+    ) {
+        let actual =
+            GameSnapshot::from_bytes(
+                b"golem",
+            );
+        let expected =
+            AppError::SnapshotDecode;
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn save(), fn load()
+    #[test]
+    fn load_should_read_back_what_save_wrote(
+    ) {
+        let clock =
+            TestClock::default();
+        let snapshot =
+            GameSnapshot::of(
+                &played_game_state(
+                    &clock,
+                ),
+            )
+            .unwrap();
+        let path =
+            std::env::temp_dir().join(
+                "golem-wordle-snapshot-test.json",
+            );
+
+        snapshot.save(&path).unwrap();
+        let actual =
+            GameSnapshot::load(&path)
+                .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(actual, snapshot);
+    }
+}
@@ -1,58 +1,470 @@
 use crate::{
+    app_error::{AppError, AppResult},
+    candidate_set::CandidateSet,
     char_result::CharResult,
-    clock::{Clock, Gmt},
-    core::{char_map_from, CharMap},
+    clock::{
+        format_duration, Clock, Gmt,
+    },
+    core::{
+        char_map_from, letter_count,
+        score_guess, CharMap,
+        GuessResult,
+    },
+    game_config::GameConfig,
+    hard_mode,
+    hint::{
+        next_hint, GivenHint, Hint,
+    },
+    keyboard::Keyboard,
 };
 use chrono::Duration;
-use std::collections::HashSet;
+use error_stack::{bail, report};
 
 pub(crate) const GAME_INSTRUCTION: &str =
     "You can continue this game by using the `continue-game` command, or you can start a new game by using the `new-game` command.";
 
+pub const HARD_MODE_NOTICE: &str =
+    "Hard mode is on: revealed hints must be used in every guess.";
+
+pub const ADVERSARIAL_NOTICE: &str =
+    "Adversarial mode is on: the word keeps changing to dodge your guesses.";
+
 pub struct GameState<'a> {
     word: &'a str,
     word_length: usize,
+    started_at: Gmt,
     last_update: Gmt,
     char_map: CharMap,
     attempts: Vec<Vec<CharResult>>,
+    attempted_at: Vec<Gmt>,
+    keyboard: Keyboard,
+    hints: Vec<GivenHint>,
+    config: GameConfig,
+    puzzle_number: Option<usize>,
+    timed_out: bool,
+    late_guess: Option<(String, Gmt)>,
+    candidates: Option<CandidateSet>,
     clock: &'a dyn Clock,
 }
 impl<'a> GameState<'a> {
     pub(crate) fn of(
         word: &'a str,
+        config: GameConfig,
         clock: &'a impl Clock,
     ) -> Self {
         let char_map =
             char_map_from(word);
+        let now = clock.now();
+        let candidates = config
+            .is_adversarial()
+            .then(|| {
+                CandidateSet::new(
+                    letter_count(word),
+                    &[],
+                )
+            });
 
         Self {
             word,
-            word_length: word.len(),
+            word_length: letter_count(
+                word,
+            ),
             char_map,
             attempts: vec![],
-            last_update: clock.now(),
+            attempted_at: vec![],
+            keyboard: Keyboard::default(
+            ),
+            hints: vec![],
+            config,
+            puzzle_number: None,
+            timed_out: false,
+            late_guess: None,
+            candidates,
+            started_at: now,
+            last_update: now,
             clock,
         }
     }
 
+    pub(crate) fn restored(
+        word: &'a str,
+        attempts: Vec<Vec<CharResult>>,
+        attempted_at: Vec<Gmt>,
+        started_at: Gmt,
+        last_update: Gmt,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> Self {
+        let candidates = config
+            .is_adversarial()
+            .then(|| {
+                CandidateSet::new(
+                    letter_count(word),
+                    &attempts,
+                )
+            });
+
+        Self {
+            keyboard: Keyboard::of(
+                &attempts,
+            ),
+            candidates,
+            attempts,
+            attempted_at,
+            started_at,
+            last_update,
+            ..Self::of(
+                word, config, clock,
+            )
+        }
+    }
+
+    pub(crate) fn with_puzzle_number(
+        self,
+        puzzle_number: usize,
+    ) -> Self {
+        Self {
+            puzzle_number: Some(
+                puzzle_number,
+            ),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_timed_out(
+        self,
+        timed_out: bool,
+    ) -> Self {
+        Self { timed_out, ..self }
+    }
+
+    pub(crate) fn with_hints(
+        mut self,
+        hints: Vec<GivenHint>,
+    ) -> Self {
+        for hint in hints {
+            self.add_hint(hint);
+        }
+
+        self
+    }
+
+    pub(crate) fn add_hint(
+        &mut self,
+        hint: GivenHint,
+    ) {
+        self.keyboard
+            .reveal(hint.hint());
+        self.hints.push(hint);
+    }
+
+    /// Reveals a letter in place or a letter that is not in the word,
+    /// using one hint of the budget.
+    pub fn hint(
+        &mut self,
+    ) -> AppResult<Hint> {
+        if self.is_finished() {
+            bail!(
+                AppError::NoGameInProgress
+            )
+        }
+
+        // a hint would tie the word down
+        if self.candidates.is_some() {
+            bail!(
+                AppError::NoHintAvailable
+            )
+        }
+
+        if self.hints_left() == 0 {
+            bail!(
+                AppError::NoHintsLeft(
+                    self.config
+                        .hint_budget()
+                )
+            )
+        }
+
+        let Some(hint) = next_hint(
+            &self.char_map,
+            &self.attempts,
+            &self.keyboard,
+            &self.hints,
+        ) else {
+            bail!(
+                AppError::NoHintAvailable
+            )
+        };
+
+        self.add_hint(GivenHint::new(
+            hint.clone(),
+            self.attempts.len(),
+        ));
+
+        Ok(hint)
+    }
+
+    pub fn hints(
+        &self,
+    ) -> &[GivenHint] {
+        &self.hints
+    }
+
+    pub fn hints_left(&self) -> usize {
+        self.config
+            .hint_budget()
+            .saturating_sub(
+                self.hints.len(),
+            )
+    }
+
     pub(crate) fn add_attempt(
         &mut self,
         attempt: Vec<CharResult>,
     ) {
+        self.keyboard.record(&attempt);
         self.attempts.push(attempt);
 
         self.last_update =
             self.clock.now();
+        self.attempted_at
+            .push(self.last_update);
     }
 
-    pub(crate) fn word(&self) -> &str {
+    pub(crate) fn word(
+        &self,
+    ) -> &'a str {
         self.word
     }
 
+    /// In adversarial mode, narrows the candidates down for the guess
+    /// and makes the word one of those left, so that scoring the
+    /// guess gives the feedback they all share.
+    pub(crate) fn dodge(
+        &mut self,
+        guess: &str,
+    ) {
+        let Some(word) = self
+            .candidates
+            .as_mut()
+            .and_then(|c| {
+                c.answer(guess)
+            })
+        else {
+            return;
+        };
+
+        self.word = word;
+        self.char_map =
+            char_map_from(word);
+    }
+
+    /// How many words still fit the guesses of an adversarial game.
+    pub fn candidate_count(
+        &self,
+    ) -> Option<usize> {
+        self.candidates
+            .as_ref()
+            .map(CandidateSet::len)
+    }
+
+    pub fn describe_candidates(
+        &self,
+    ) -> Option<String> {
+        self.candidate_count().map(
+            |count| match count {
+                1 => "Only 1 word still fits your guesses.".to_string(),
+                n => format!("{} words still fit your guesses.", n),
+            },
+        )
+    }
+
+    pub fn revealed_word(
+        &self,
+    ) -> Option<&'a str> {
+        self.is_finished()
+            .then_some(self.word)
+    }
+
     pub fn word_length(&self) -> usize {
         self.word_length
     }
 
+    pub fn config(
+        &self,
+    ) -> &GameConfig {
+        &self.config
+    }
+
+    /// When each attempt was made. Games restored from a snapshot
+    /// taken before these were kept fall back to the last update.
+    pub fn attempted_at(
+        &self,
+    ) -> Vec<Gmt> {
+        (0..self.attempts.len())
+            .map(|i| {
+                self.attempted_at
+                    .get(i)
+                    .copied()
+                    .unwrap_or(
+                        self.last_update,
+                    )
+            })
+            .collect()
+    }
+
+    pub fn puzzle_number(
+        &self,
+    ) -> Option<usize> {
+        self.puzzle_number
+    }
+
+    pub fn attempts(
+        &self,
+    ) -> &[Vec<CharResult>] {
+        &self.attempts
+    }
+
+    pub fn keyboard(
+        &self,
+    ) -> &Keyboard {
+        &self.keyboard
+    }
+
+    pub fn started_at(&self) -> Gmt {
+        self.started_at
+    }
+
+    pub fn last_update(&self) -> Gmt {
+        self.last_update
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.attempts.last().is_some_and(
+            |attempt| {
+                !attempt.is_empty()
+                    && attempt.iter().all(
+                        |r| {
+                            *r.result() == GuessResult::Correct
+                        },
+                    )
+            },
+        )
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.timed_out
+            || self.is_won()
+            || self.attempts_left() == 0
+    }
+
+    /// Whether a guess came in after the deadline of a timed game.
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// The earlier of the game and the guess deadlines, if the game
+    /// is timed.
+    pub fn deadline(
+        &self,
+    ) -> Option<Gmt> {
+        let game = self
+            .config
+            .game_time_limit()
+            .map(|limit| {
+                self.started_at + limit
+            });
+        let guess = self
+            .config
+            .guess_time_limit()
+            .map(|limit| {
+                self.last_update + limit
+            });
+
+        game.into_iter()
+            .chain(guess)
+            .min()
+    }
+
+    pub fn time_left(
+        &self,
+    ) -> Option<Duration> {
+        self.deadline().map(
+            |deadline| {
+                (deadline
+                    - self.clock.now())
+                .max(Duration::zero())
+            },
+        )
+    }
+
+    /// Time spent on the game, up to its end once it is over.
+    pub fn elapsed(&self) -> Duration {
+        let end = if self.is_finished()
+        {
+            self.last_update
+        } else {
+            self.clock.now()
+        };
+
+        end - self.started_at
+    }
+
+    pub(crate) fn is_past_deadline(
+        &self,
+    ) -> bool {
+        self.deadline().is_some_and(
+            |deadline| {
+                self.clock.now()
+                    > deadline
+            },
+        )
+    }
+
+    /// Ends the game on a guess that came in after the deadline. The
+    /// guess is kept, though not played, so that a replay can show it.
+    pub(crate) fn time_out(
+        &mut self,
+        user_input: &str,
+    ) {
+        self.late_guess = Some((
+            user_input.to_string(),
+            self.clock.now(),
+        ));
+        self.last_update = self
+            .deadline()
+            .unwrap_or_else(|| {
+                self.clock.now()
+            });
+        self.timed_out = true;
+    }
+
+    /// The guess that ended a timed game, and when it came in.
+    pub fn late_guess(
+        &self,
+    ) -> Option<(&str, Gmt)> {
+        self.late_guess.as_ref().map(
+            |(guess, time)| {
+                (guess.as_str(), *time)
+            },
+        )
+    }
+
+    /// How long a timed game has lasted and how long is left.
+    pub fn describe_time(
+        &self,
+    ) -> Option<String> {
+        self.time_left().map(|left| {
+            format!(
+                "Time played: {}, time left: {}.",
+                format_duration(
+                    self.elapsed()
+                ),
+                format_duration(left)
+            )
+        })
+    }
+
     pub(crate) fn last_update_older_than(
         &self,
         duration: &Duration,
@@ -62,27 +474,76 @@ impl<'a> GameState<'a> {
             > *duration
     }
 
-    pub(crate) fn find_by(
+    pub(crate) fn score(
         &self,
-        char: &char,
-    ) -> Option<&HashSet<usize>> {
-        self.char_map.get(char)
+        guess: &str,
+    ) -> Vec<CharResult> {
+        score_guess(
+            guess,
+            &self.char_map,
+        )
+    }
+
+    pub(crate) fn check_hard_mode(
+        &self,
+        guess: &str,
+    ) -> AppResult<()> {
+        if !self.config.hard_mode() {
+            return Ok(());
+        }
+
+        hard_mode::check_guess(
+            guess,
+            &self.attempts,
+        )
+        .map_err(|rule| {
+            report!(AppError::HardModeViolation(rule))
+        })
     }
 
-    pub(crate) fn attempts_left(
+    pub fn attempts_left(
         &self,
     ) -> usize {
-        self.word_length
-            - self.attempts.len()
+        self.config
+            .max_attempts()
+            .saturating_sub(
+                self.attempts.len(),
+            )
     }
 
     pub fn describe(
         &self,
     ) -> Vec<String> {
-        let mut result = vec![format!(
+        let mut result = vec![];
+
+        if let Some(number) =
+            self.puzzle_number
+        {
+            result.push(format!(
+                "Golem Wordle #{}",
+                number
+            ));
+        }
+
+        result.push(format!(
             "Welcome to Golem Wordle! Please describe Golem in a {}-letter word.",
             self.word_length
-        )];
+        ));
+
+        if self.config.hard_mode() {
+            result.push(
+                HARD_MODE_NOTICE
+                    .to_string(),
+            );
+        }
+
+        if self.config.is_adversarial()
+        {
+            result.push(
+                ADVERSARIAL_NOTICE
+                    .to_string(),
+            );
+        }
 
         let count = self.attempts.len();
 
@@ -95,6 +556,11 @@ impl<'a> GameState<'a> {
 
             attempts.insert(0, format!("Here are your previous {} guesses.", count));
 
+            attempts.extend(
+                self.keyboard
+                    .describe(),
+            );
+
             attempts.push(
                 format!("Last time you played was on {}.", self.last_update)
             );
@@ -106,11 +572,23 @@ impl<'a> GameState<'a> {
 
         result.extend(attempts);
 
+        result.extend(
+            self.hints.iter().map(
+                |h| {
+                    h.hint().to_string()
+                },
+            ),
+        );
+
         result.push(format!(
             "You had {} attempts left.",
             self.attempts_left()
         ));
 
+        result.extend(
+            self.describe_time(),
+        );
+
         result.push(
             GAME_INSTRUCTION
                 .to_string(),
@@ -123,7 +601,12 @@ impl<'a> GameState<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clock::RealClock;
+    use crate::{
+        assert_app_error,
+        clock::RealClock,
+        game_config::DEFAULT_MAX_ATTEMPTS,
+        keyboard::LetterState,
+    };
     use chrono::{
         Datelike, TimeZone, Utc,
     };
@@ -146,7 +629,9 @@ mod tests {
     ) -> GameState {
         let mut game_state =
             GameState::of(
-                word, &RealClock,
+                word,
+                GameConfig::default(),
+                &RealClock,
             );
 
         game_state.last_update =
@@ -155,7 +640,7 @@ mod tests {
         game_state
     }
 
-    // fn of(), fn word(), fn word_length(), fn find_by()
+    // fn of(), fn word(), fn word_length()
     #[test]
     fn game_state_should_be_created_with_expected_state(
     ) {
@@ -176,26 +661,55 @@ mod tests {
             .is_empty());
         assert_eq!(
             game_state.attempts_left(),
-            word.len()
+            DEFAULT_MAX_ATTEMPTS
         );
         assert_eq!(
             game_state.last_update,
             *TEST_DATE_TIME
         );
         assert_eq!(
-            game_state.find_by(&'a'),
+            game_state
+                .char_map
+                .get("a"),
             Some(&hashset! {0})
         );
         assert_eq!(
-            game_state.find_by(&'b'),
+            game_state
+                .char_map
+                .get("b"),
             Some(&hashset! {1, 2})
         );
         assert_eq!(
-            game_state.find_by(&'c'),
+            game_state
+                .char_map
+                .get("c"),
             Some(&hashset! {3})
         );
     }
 
+    // fn score()
+    #[test]
+    fn score_should_mark_a_repeated_letter_only_once(
+    ) {
+        let game_state =
+            new_test_game_state(
+                "golem",
+            );
+
+        let actual =
+            game_state.score("lllll");
+
+        let expected = vec![
+            CharResult::absent('l'),
+            CharResult::absent('l'),
+            CharResult::correct('l'),
+            CharResult::absent('l'),
+            CharResult::absent('l'),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
     // fn add_attempt()
     #[test]
     fn add_attempt_should_update_game_state(
@@ -209,7 +723,7 @@ mod tests {
             .is_empty());
         assert_eq!(
             game_state.attempts_left(),
-            word.len()
+            DEFAULT_MAX_ATTEMPTS
         );
         assert_eq!(
             game_state.last_update,
@@ -236,7 +750,7 @@ mod tests {
         );
         assert_eq!(
             game_state.attempts_left(),
-            word.len() - 1
+            DEFAULT_MAX_ATTEMPTS - 1
         );
         assert_ne!(
             game_state
@@ -248,9 +762,185 @@ mod tests {
             game_state.last_update,
             *TEST_DATE_TIME
         );
+        assert_eq!(
+            game_state
+                .keyboard()
+                .state_of("b"),
+            LetterState::Present
+        );
+        assert_eq!(
+            game_state
+                .keyboard()
+                .state_of("x"),
+            LetterState::Absent
+        );
+    }
+
+    // fn attempts_left()
+    #[test]
+    fn attempts_left_should_depend_on_max_attempts_rather_than_word_length(
+    ) {
+        let config = GameConfig::new(
+            2, 1, 32, false,
+        )
+        .unwrap();
+        let mut game_state =
+            GameState::of(
+                "distributed",
+                config,
+                &RealClock,
+            );
+
+        assert_eq!(
+            game_state.attempts_left(),
+            2
+        );
+
+        game_state.add_attempt(vec![]);
+        game_state.add_attempt(vec![]);
+        game_state.add_attempt(vec![]);
+
+        assert_eq!(
+            game_state.attempts_left(),
+            0
+        );
+    }
+
+    // fn is_won(), fn is_finished(), fn revealed_word()
+    #[test]
+    fn is_won_should_be_false_when_the_last_attempt_is_empty(
+    ) {
+        let mut game_state =
+            new_test_game_state("abc");
+
+        game_state.add_attempt(vec![]);
+
+        assert!(!game_state.is_won());
+    }
+
+    #[test]
+    fn revealed_word_should_only_be_available_once_the_game_is_finished(
+    ) {
+        let config = GameConfig::new(
+            2, 1, 32, false,
+        )
+        .unwrap();
+        let mut game_state =
+            GameState::of(
+                "abc", config,
+                &RealClock,
+            );
+
+        assert!(!game_state.is_won());
+        assert!(
+            !game_state.is_finished()
+        );
+        assert_eq!(
+            game_state.revealed_word(),
+            None
+        );
+
+        game_state.add_attempt(
+            game_state.score("abx"),
+        );
+
+        assert!(
+            !game_state.is_finished()
+        );
+
+        game_state.add_attempt(
+            game_state.score("abc"),
+        );
+
+        assert!(game_state.is_won());
+        assert!(
+            game_state.is_finished()
+        );
+        assert_eq!(
+            game_state.revealed_word(),
+            Some("abc")
+        );
+    }
+
+    // fn check_hard_mode()
+    fn attempt_revealing_e(
+    ) -> Vec<CharResult> {
+        vec![
+            CharResult::absent('s'),
+            CharResult::absent('p'),
+            CharResult::absent('i'),
+            CharResult::absent('c'),
+            CharResult::present('e'),
+        ]
+    }
+
+    #[test]
+    fn check_hard_mode_should_ignore_revealed_hints_when_hard_mode_is_off(
+    ) {
+        let mut game_state =
+            new_test_game_state(
+                "golem",
+            );
+        game_state.add_attempt(
+            attempt_revealing_e(),
+        );
+
+        let actual = game_state
+            .check_hard_mode("board");
+
+        assert!(actual.is_ok());
+    }
+
+    #[test]
+    fn check_hard_mode_should_reject_a_guess_ignoring_revealed_hints_when_hard_mode_is_on(
+    ) {
+        let config = GameConfig::new(
+            6, 1, 32, true,
+        )
+        .unwrap();
+        let mut game_state =
+            GameState::of(
+                "golem", config,
+                &RealClock,
+            );
+        game_state.add_attempt(
+            attempt_revealing_e(),
+        );
+
+        let actual = game_state
+            .check_hard_mode("board");
+        let expected =
+            AppError::HardModeViolation(
+                hard_mode::HardModeRule::ReusePresentLetter {
+                    letter: "e".to_string(),
+                    count: 1,
+                },
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
     }
 
     // fn describe()
+    #[test]
+    fn describe_should_start_with_the_puzzle_number_of_a_daily_game(
+    ) {
+        let actual =
+            new_test_game_state("abc")
+                .with_puzzle_number(123)
+                .describe();
+
+        assert_eq!(
+            actual[0],
+            "Golem Wordle #123"
+        );
+        assert_eq!(
+            actual[1],
+            "Welcome to Golem Wordle! Please describe Golem in a 3-letter word."
+        );
+    }
+
     #[test]
     fn describe_should_not_contain_any_previous_guesses_when_attempts_are_empty(
     ) {
@@ -261,7 +951,7 @@ mod tests {
         let expected = vec![
             "Welcome to Golem Wordle! Please describe Golem in a 3-letter word.",
             "You started this game on 2312-12-18 19:23:00 UTC.",
-            "You had 3 attempts left.",
+            "You had 6 attempts left.",
             GAME_INSTRUCTION,
         ];
 
@@ -295,7 +985,7 @@ mod tests {
             "['a' => Correct, 'x' => Absent, 'b' => Present]",
             "['a' => Correct, 'y' => Absent, 'b' => Present]",
             "Last time you played was on 2312-12-18 19:23:00 UTC.",
-            "You had 1 attempts left.",
+            "You had 4 attempts left.",
             GAME_INSTRUCTION,
         ];
 
@@ -0,0 +1,214 @@
+use crate::{
+    char_result::CharResult,
+    core::{letters_of, GuessResult},
+};
+use std::fmt::{
+    self, Display, Formatter,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HardModeRule {
+    KeepCorrectLetter {
+        letter: String,
+        position: usize,
+    },
+    ReusePresentLetter {
+        letter: String,
+        count: usize,
+    },
+}
+
+impl Display for HardModeRule {
+    fn fmt(
+        &self,
+        f: &mut Formatter,
+    ) -> fmt::Result {
+        match self {
+            HardModeRule::KeepCorrectLetter {
+                letter,
+                position,
+            } => {
+                write!(
+                    f,
+                    "letter '{}' must stay in position {}",
+                    letter,
+                    position + 1
+                )
+            }
+            HardModeRule::ReusePresentLetter {
+                letter,
+                count,
+            } => {
+                write!(
+                    f,
+                    "letter '{}' must be used at least {} time(s)",
+                    letter, count
+                )
+            }
+        }
+    }
+}
+
+pub(crate) fn check_guess(
+    guess: &str,
+    attempts: &[Vec<CharResult>],
+) -> Result<(), HardModeRule> {
+    let guess = letters_of(guess);
+
+    for attempt in attempts {
+        for (position, r) in
+            attempt.iter().enumerate()
+        {
+            if *r.result()
+                == GuessResult::Correct
+                && guess.get(position)
+                    != Some(&r.letter())
+            {
+                return Err(HardModeRule::KeepCorrectLetter {
+                    letter: r.letter().to_string(),
+                    position,
+                });
+            }
+        }
+
+        let mut required: Vec<(
+            &str,
+            usize,
+        )> = vec![];
+
+        for r in attempt.iter().filter(
+            |r| {
+                *r.result()
+                    != GuessResult::Absent
+            },
+        ) {
+            match required
+                .iter_mut()
+                .find(|(c, _)| {
+                    *c == r.letter()
+                }) {
+                Some((_, count)) => {
+                    *count += 1
+                }
+                None => required
+                    .push((r.letter(), 1)),
+            }
+        }
+
+        for (letter, count) in required
+        {
+            let used = guess
+                .iter()
+                .filter(|c| {
+                    **c == letter
+                })
+                .count();
+
+            if used < count {
+                return Err(HardModeRule::ReusePresentLetter {
+                    letter: letter.to_string(),
+                    count,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    fn attempts() -> Vec<Vec<CharResult>>
+    {
+        vec![vec![
+            CharResult::absent('s'),
+            CharResult::absent('p'),
+            CharResult::absent('i'),
+            CharResult::absent('c'),
+            CharResult::present('e'),
+        ]]
+    }
+
+    fn more_attempts(
+    ) -> Vec<Vec<CharResult>> {
+        let mut attempts = attempts();
+        attempts.push(vec![
+            CharResult::absent('h'),
+            CharResult::correct('o'),
+            CharResult::absent('t'),
+            CharResult::correct('e'),
+            CharResult::present('l'),
+        ]);
+        attempts
+    }
+
+    // fn check_guess()
+    #[test_case(
+        "spice",
+        vec![] ;
+        "any guess is allowed before the first attempt"
+    )]
+    #[test_case(
+        "hotel",
+        attempts() ;
+        "a guess reusing the Present letter is allowed"
+    )]
+    #[test_case(
+        "golem",
+        more_attempts() ;
+        "a guess keeping every Correct letter in place is allowed"
+    )]
+    fn check_guess_should_accept_guesses_that_use_every_revealed_hint(
+        guess: &str,
+        attempts: Vec<Vec<CharResult>>,
+    ) {
+        let actual = check_guess(
+            guess, &attempts,
+        );
+
+        assert_eq!(actual, Ok(()));
+    }
+
+    #[test_case(
+        "board",
+        attempts(),
+        HardModeRule::ReusePresentLetter { letter: "e".to_string(), count: 1 } ;
+        "a guess dropping a Present letter breaks the ReusePresentLetter rule"
+    )]
+    #[test_case(
+        "movie",
+        more_attempts(),
+        HardModeRule::KeepCorrectLetter { letter: "e".to_string(), position: 3 } ;
+        "a guess moving a Correct letter breaks the KeepCorrectLetter rule"
+    )]
+    #[test_case(
+        "spice",
+        vec![vec![
+            CharResult::present('e'),
+            CharResult::absent('x'),
+            CharResult::present('e'),
+            CharResult::absent('y'),
+            CharResult::absent('z'),
+        ]],
+        HardModeRule::ReusePresentLetter { letter: "e".to_string(), count: 2 } ;
+        "a guess must reuse a Present letter as many times as it was revealed"
+    )]
+    fn check_guess_should_reject_guesses_that_break_a_rule(
+        guess: &str,
+        attempts: Vec<Vec<CharResult>>,
+        expected: HardModeRule,
+    ) {
+        let actual = check_guess(
+            guess, &attempts,
+        );
+
+        assert_eq!(
+            actual,
+            Err(expected)
+        );
+    }
+}
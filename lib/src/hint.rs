@@ -0,0 +1,306 @@
+use crate::{
+    char_result::CharResult,
+    core::{
+        letters_of, CharMap,
+        GuessResult,
+    },
+    keyboard::{Keyboard, LetterState},
+    word_list::with_words,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Formatter},
+};
+
+pub const DEFAULT_HINT_BUDGET: usize =
+    2;
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub enum Hint {
+    CorrectLetter {
+        letter: String,
+        position: usize,
+    },
+    AbsentLetter {
+        letter: String,
+    },
+}
+
+impl Display for Hint {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Hint::CorrectLetter {
+                letter,
+                position,
+            } => write!(
+                f,
+                "The letter '{}' is in position {}.",
+                letter,
+                position + 1
+            ),
+            Hint::AbsentLetter {
+                letter,
+            } => write!(
+                f,
+                "The letter '{}' is not in the word.",
+                letter
+            ),
+        }
+    }
+}
+
+/// A hint along with the number of guesses made before it was given.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct GivenHint {
+    hint: Hint,
+    after_attempts: usize,
+}
+impl GivenHint {
+    pub(crate) fn new(
+        hint: Hint,
+        after_attempts: usize,
+    ) -> Self {
+        Self {
+            hint,
+            after_attempts,
+        }
+    }
+
+    pub fn hint(&self) -> &Hint {
+        &self.hint
+    }
+
+    pub fn after_attempts(
+        &self,
+    ) -> usize {
+        self.after_attempts
+    }
+}
+
+/// The leftmost letter of the word that no guess or hint has put in
+/// place yet, or else the first letter of the word list that is
+/// neither in the word nor tried yet.
+pub(crate) fn next_hint(
+    char_map: &CharMap,
+    attempts: &[Vec<CharResult>],
+    keyboard: &Keyboard,
+    given: &[GivenHint],
+) -> Option<Hint> {
+    let revealed: BTreeSet<usize> =
+        attempts
+            .iter()
+            .flat_map(|attempt| {
+                attempt
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| {
+                        *r.result() == GuessResult::Correct
+                    })
+                    .map(|(i, _)| i)
+            })
+            .chain(given.iter().filter_map(
+                |g| match &g.hint {
+                    Hint::CorrectLetter {
+                        position,
+                        ..
+                    } => Some(*position),
+                    Hint::AbsentLetter {
+                        ..
+                    } => None,
+                },
+            ))
+            .collect();
+
+    let correct = char_map
+        .iter()
+        .flat_map(
+            |(letter, positions)| {
+                positions.iter().map(
+                    move |p| {
+                        (*p, letter)
+                    },
+                )
+            },
+        )
+        .filter(|(p, _)| {
+            !revealed.contains(p)
+        })
+        .min();
+
+    if let Some((position, letter)) =
+        correct
+    {
+        return Some(
+            Hint::CorrectLetter {
+                letter: letter.clone(),
+                position,
+            },
+        );
+    }
+
+    let alphabet: BTreeSet<String> =
+        with_words(|words| {
+            words
+                .iter()
+                .flat_map(|w| {
+                    letters_of(w)
+                })
+                .map(String::from)
+                .collect()
+        });
+
+    alphabet
+        .into_iter()
+        .find(|letter| {
+            !char_map.contains_key(letter)
+                && keyboard.state_of(letter)
+                    == LetterState::Unknown
+        })
+        .map(|letter| Hint::AbsentLetter {
+            letter,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        char_map_from, score_guess,
+    };
+    use pretty_assertions::{
+        assert_eq, assert_ne,
+    };
+
+    fn hint_after(
+        word: &str,
+        guesses: &[&str],
+        given: &[GivenHint],
+    ) -> Option<Hint> {
+        let char_map =
+            char_map_from(word);
+        let attempts: Vec<_> = guesses
+            .iter()
+            .map(|guess| {
+                score_guess(
+                    guess, &char_map,
+                )
+            })
+            .collect();
+        let mut keyboard =
+            Keyboard::of(&attempts);
+
+        for g in given {
+            keyboard.reveal(g.hint());
+        }
+
+        next_hint(
+            &char_map, &attempts,
+            &keyboard, given,
+        )
+    }
+
+    // fn next_hint()
+    #[test]
+    fn next_hint_should_reveal_the_leftmost_letter_not_in_place_yet(
+    ) {
+        let actual = hint_after(
+            "golem",
+            &["globe"],
+            &[],
+        );
+        let expected =
+            Some(Hint::CorrectLetter {
+                letter: "o".to_string(),
+                position: 1,
+            });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn next_hint_should_skip_positions_already_hinted(
+    ) {
+        let given = [GivenHint::new(
+            Hint::CorrectLetter {
+                letter: "o".to_string(),
+                position: 1,
+            },
+            1,
+        )];
+
+        let actual = hint_after(
+            "golem",
+            &["globe"],
+            &given,
+        );
+        let expected =
+            Some(Hint::CorrectLetter {
+                letter: "l".to_string(),
+                position: 2,
+            });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn next_hint_should_rule_out_an_untried_letter_once_every_letter_is_in_place(
+    ) {
+        let actual = hint_after(
+            "golem",
+            &["golem"],
+            &[GivenHint::new(
+                Hint::AbsentLetter {
+                    letter: "a"
+                        .to_string(),
+                },
+                1,
+            )],
+        )
+        .unwrap();
+
+        match actual {
+            Hint::AbsentLetter {
+                letter,
+            } => {
+                assert_ne!(letter, "a");
+                assert!(!"golem"
+                    .contains(&letter));
+            }
+            hint => panic!(
+                "unexpected hint: {hint:?}"
+            ),
+        }
+    }
+
+    // fn fmt()
+    #[test]
+    fn fmt_should_count_positions_from_one(
+    ) {
+        let hint =
+            Hint::CorrectLetter {
+                letter: "g".to_string(),
+                position: 0,
+            };
+
+        assert_eq!(
+            hint.to_string(),
+            "The letter 'g' is in position 1."
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    char_result::CharResult,
+    core::GuessResult, hint::Hint,
+};
+use std::collections::BTreeMap;
+
+/// What the guesses so far tell about a letter, from least to best
+/// known.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum LetterState {
+    Unknown,
+    Absent,
+    Present,
+    Correct,
+}
+
+impl From<&GuessResult>
+    for LetterState
+{
+    fn from(
+        result: &GuessResult,
+    ) -> Self {
+        match result {
+            GuessResult::Correct => {
+                Self::Correct
+            }
+            GuessResult::Present => {
+                Self::Present
+            }
+            GuessResult::Absent => {
+                Self::Absent
+            }
+        }
+    }
+}
+
+/// The best known state of every letter guessed so far, so that
+/// players do not have to read back through their guesses.
+#[derive(
+    Clone, Debug, Default, PartialEq,
+)]
+pub struct Keyboard(
+    BTreeMap<String, LetterState>,
+);
+impl Keyboard {
+    pub(crate) fn of(
+        attempts: &[Vec<CharResult>],
+    ) -> Self {
+        let mut result =
+            Self::default();
+
+        for attempt in attempts {
+            result.record(attempt);
+        }
+
+        result
+    }
+
+    /// A letter found in place somewhere stays correct even when
+    /// another guess shows it elsewhere, and a present letter is not
+    /// ruled out by a repeat of it that scores absent.
+    pub(crate) fn record(
+        &mut self,
+        attempt: &[CharResult],
+    ) {
+        for r in attempt {
+            self.learn(
+                r.letter(),
+                r.result().into(),
+            );
+        }
+    }
+
+    pub(crate) fn reveal(
+        &mut self,
+        hint: &Hint,
+    ) {
+        match hint {
+            Hint::CorrectLetter {
+                letter,
+                ..
+            } => self.learn(
+                letter,
+                LetterState::Correct,
+            ),
+            Hint::AbsentLetter {
+                letter,
+            } => self.learn(
+                letter,
+                LetterState::Absent,
+            ),
+        }
+    }
+
+    fn learn(
+        &mut self,
+        letter: &str,
+        state: LetterState,
+    ) {
+        let known = self
+            .0
+            .entry(letter.to_string())
+            .or_insert(state);
+
+        *known = (*known).max(state);
+    }
+
+    pub fn state_of(
+        &self,
+        letter: &str,
+    ) -> LetterState {
+        self.0
+            .get(letter)
+            .copied()
+            .unwrap_or(
+                LetterState::Unknown,
+            )
+    }
+
+    /// The guessed letters in alphabetical order.
+    pub fn letters(
+        &self,
+    ) -> impl Iterator<
+        Item = (&str, LetterState),
+    > {
+        self.0.iter().map(
+            |(letter, state)| {
+                (
+                    letter.as_str(),
+                    *state,
+                )
+            },
+        )
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        [
+            (
+                LetterState::Correct,
+                "Letters in the right spot",
+            ),
+            (
+                LetterState::Present,
+                "Letters in the word, not yet in place",
+            ),
+            (
+                LetterState::Absent,
+                "Letters not in the word",
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(state, label)| {
+            let letters: Vec<_> = self
+                .letters()
+                .filter(|(_, s)| {
+                    *s == state
+                })
+                .map(|(letter, _)| letter)
+                .collect();
+
+            (!letters.is_empty())
+                .then(|| {
+                    format!(
+                        "{}: {}.",
+                        label,
+                        letters.join(", ")
+                    )
+                })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        char_map_from, score_guess,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn keyboard_after(
+        word: &str,
+        guesses: &[&str],
+    ) -> Keyboard {
+        let attempts: Vec<_> = guesses
+            .iter()
+            .map(|guess| {
+                score_guess(
+                    guess,
+                    &char_map_from(
+                        word,
+                    ),
+                )
+            })
+            .collect();
+
+        Keyboard::of(&attempts)
+    }
+
+    // fn record()
+    #[test]
+    fn record_should_keep_the_best_known_state_of_each_letter(
+    ) {
+        // 'o' scores present and absent in "spoon", then correct
+        let actual = keyboard_after(
+            "golem",
+            &[
+                "spoon", "globe",
+                "motor",
+            ],
+        );
+
+        assert_eq!(
+            actual.state_of("o"),
+            LetterState::Correct
+        );
+        assert_eq!(
+            actual.state_of("g"),
+            LetterState::Correct
+        );
+        assert_eq!(
+            actual.state_of("m"),
+            LetterState::Present
+        );
+        assert_eq!(
+            actual.state_of("s"),
+            LetterState::Absent
+        );
+        assert_eq!(
+            actual.state_of("z"),
+            LetterState::Unknown
+        );
+    }
+
+    #[test]
+    fn record_should_not_rule_out_a_present_letter_guessed_twice(
+    ) {
+        // only the first 'e' of "eerie" scores present
+        let actual = keyboard_after(
+            "golem",
+            &["eerie"],
+        );
+
+        assert_eq!(
+            actual.state_of("e"),
+            LetterState::Present
+        );
+        assert_eq!(
+            actual.state_of("r"),
+            LetterState::Absent
+        );
+    }
+
+    // fn describe()
+    #[test]
+    fn describe_should_group_letters_by_state(
+    ) {
+        let actual = keyboard_after(
+            "golem",
+            &["spice"],
+        );
+        let expected = vec![
+            "Letters in the word, not yet in place: e.",
+            "Letters not in the word: c, i, p, s.",
+        ];
+
+        assert_eq!(
+            actual.describe(),
+            expected
+        );
+    }
+}
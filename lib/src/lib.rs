@@ -1,9 +1,28 @@
 // #![doc = include_str!("../README.md")]
 
 pub mod app_error;
-mod app_state;
-mod char_result;
+pub mod app_state;
+pub mod bench;
+pub mod candidate_set;
+pub mod challenge;
+pub mod char_result;
 pub mod clock;
 pub mod core;
-mod game_state;
+pub mod daily;
+pub mod game_config;
+pub mod game_history;
+pub mod game_limits;
+pub mod game_snapshot;
+pub mod game_state;
+pub mod hard_mode;
+pub mod hint;
+pub mod keyboard;
+pub mod multi_game_state;
+pub mod player_stats;
+pub mod race;
 pub mod session_state;
+pub mod share_grid;
+pub mod solver;
+pub mod word_list;
+pub mod word_list_report;
+pub mod word_picker;
@@ -0,0 +1,634 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    char_result::CharResult,
+    clock::{Clock, Gmt},
+    core::{
+        letter_count, normalized,
+        words_for,
+    },
+    game_config::GameConfig,
+    game_state::GameState,
+    session_state::SessionState,
+    word_picker::WordPicker,
+};
+use chrono::Duration;
+use error_stack::bail;
+use std::collections::BTreeMap;
+
+/// Two boards as in Dordle, or four as in Quordle.
+pub const BOARD_COUNTS: [usize; 2] =
+    [2, 4];
+
+/// Attempts on top of one per board, so that two boards get 7 and
+/// four get 9.
+pub const EXTRA_ATTEMPTS: usize = 5;
+
+/// How many times the picker is asked for a word that fits before
+/// giving up.
+const MAX_PICKS: usize = 100;
+
+pub fn default_max_attempts(
+    board_count: usize,
+) -> usize {
+    board_count + EXTRA_ATTEMPTS
+}
+
+/// What a guess did to every board of a multi-board game.
+#[derive(Debug, PartialEq)]
+pub struct MultiSessionState {
+    overall: SessionState,
+    boards: Vec<Option<SessionState>>,
+}
+impl MultiSessionState {
+    /// Won once every board is solved, lost or timed out as soon as
+    /// one board is.
+    pub fn overall(
+        &self,
+    ) -> &SessionState {
+        &self.overall
+    }
+
+    /// The outcome of the guess on each board, or `None` for the
+    /// boards solved before it.
+    pub fn boards(
+        &self,
+    ) -> &[Option<SessionState>] {
+        &self.boards
+    }
+
+    pub fn is_over(&self) -> bool {
+        !matches!(
+            self.overall,
+            SessionState::InProgress { .. }
+        )
+    }
+
+    pub fn messages(
+        &self,
+    ) -> Vec<String> {
+        self.overall.messages()
+    }
+}
+
+/// Several words of the same length guessed at once: every guess is
+/// played on each board not solved yet, out of one shared budget of
+/// attempts.
+pub struct MultiGameState<'a> {
+    boards: Vec<GameState<'a>>,
+}
+impl<'a> MultiGameState<'a> {
+    pub fn of(
+        words: &[&'a str],
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<Self> {
+        check_board_count(words.len())?;
+
+        if words.iter().any(|w| {
+            letter_count(w)
+                != letter_count(
+                    words[0],
+                )
+        }) {
+            bail!(AppError::InvalidGameConfig(
+                "the words of a multi-board game must have the same length".to_string()
+            ))
+        }
+
+        Ok(Self {
+            boards: words
+                .iter()
+                .map(|w| {
+                    GameState::of(
+                        w,
+                        config.clone(),
+                        clock,
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// Picks a different word of the same length for every board.
+    pub fn new_with(
+        picker: &mut dyn WordPicker,
+        board_count: usize,
+        config: GameConfig,
+        clock: &'a impl Clock,
+    ) -> AppResult<Self> {
+        check_board_count(board_count)?;
+
+        let mut counts =
+            BTreeMap::new();
+        for word in words_for(&config)?
+        {
+            *counts
+                .entry(letter_count(
+                    word,
+                ))
+                .or_insert(0) += 1;
+        }
+
+        let first = (0..MAX_PICKS)
+            .map(|_| picker.pick(&config))
+            .find(|word| match word {
+                Ok(w) => counts
+                    .get(&letter_count(w))
+                    .is_some_and(|n| {
+                        *n >= board_count
+                    }),
+                // stop at the first error
+                Err(_) => true,
+            })
+            .transpose()?;
+
+        let Some(first) = first else {
+            bail!(not_enough_words(
+                board_count
+            ))
+        };
+
+        let same_length = config
+            .clone()
+            .with_word_length(
+                letter_count(first),
+            );
+        let mut words = vec![first];
+
+        for _ in 0..MAX_PICKS {
+            if words.len()
+                == board_count
+            {
+                break;
+            }
+
+            let word = picker
+                .pick(&same_length)?;
+
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        }
+
+        if words.len() < board_count {
+            bail!(not_enough_words(
+                board_count
+            ))
+        }
+
+        Self::of(&words, config, clock)
+    }
+
+    pub fn boards(
+        &self,
+    ) -> &[GameState<'a>] {
+        &self.boards
+    }
+
+    pub fn config(
+        &self,
+    ) -> &GameConfig {
+        self.boards[0].config()
+    }
+
+    pub fn word_length(&self) -> usize {
+        self.boards[0].word_length()
+    }
+
+    /// Guesses made so far. Boards still in play got every one.
+    pub fn guesses(&self) -> usize {
+        self.boards
+            .iter()
+            .map(|b| b.attempts().len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn attempts_left(
+        &self,
+    ) -> usize {
+        self.config()
+            .max_attempts()
+            .saturating_sub(
+                self.guesses(),
+            )
+    }
+
+    pub fn solved_count(
+        &self,
+    ) -> usize {
+        self.boards
+            .iter()
+            .filter(|b| b.is_won())
+            .count()
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.boards
+            .iter()
+            .all(GameState::is_won)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.boards.iter().any(|b| {
+            b.is_finished()
+                && !b.is_won()
+        }) || self.is_won()
+    }
+
+    pub fn started_at(&self) -> Gmt {
+        self.boards[0].started_at()
+    }
+
+    pub fn last_update(&self) -> Gmt {
+        self.boards
+            .iter()
+            .map(GameState::last_update)
+            .max()
+            .unwrap_or(
+                self.started_at(),
+            )
+    }
+
+    pub(crate) fn last_update_older_than(
+        &self,
+        duration: &Duration,
+    ) -> bool {
+        self.boards.iter().all(|b| {
+            b.last_update_older_than(
+                duration,
+            )
+        })
+    }
+
+    /// Plays the guess on every board not solved yet. Nothing is
+    /// played unless the guess is fine for all of them.
+    pub fn guess(
+        &mut self,
+        user_input: &str,
+    ) -> AppResult<MultiSessionState>
+    {
+        if self.is_finished() {
+            bail!(
+                AppError::NoGameInProgress
+            )
+        }
+
+        // a late guess ends the game, whatever it is
+        if self.boards.iter().any(|b| {
+            !b.is_won()
+                && b.is_past_deadline()
+        }) {
+            for board in
+                &mut self.boards
+            {
+                if !board.is_won() {
+                    board.time_out(
+                        user_input,
+                    );
+                }
+            }
+
+            return Ok(MultiSessionState {
+                overall: SessionState::TimedOut(
+                    format!(
+                        "Time's up! The words were {}.",
+                        self.describe_words()
+                    ),
+                ),
+                boards: self
+                    .boards
+                    .iter()
+                    .map(|_| None)
+                    .collect(),
+            });
+        }
+
+        let mut user_input =
+            normalized(user_input);
+        for board in &self.boards {
+            if !board.is_won() {
+                user_input =
+                    SessionState::checked_guess(
+                        &user_input,
+                        board,
+                    )?;
+            }
+        }
+
+        let boards: Vec<_> = self
+            .boards
+            .iter_mut()
+            .map(|board| {
+                (!board.is_won()).then(|| {
+                    SessionState::after_guess(
+                        &user_input,
+                        board,
+                    )
+                })
+            })
+            .collect();
+
+        let overall = if self.is_won() {
+            SessionState::Won(format!(
+                "Well done, you've solved all {} boards in {} guesses!",
+                self.boards.len(),
+                self.guesses()
+            ))
+        } else if self.is_finished() {
+            SessionState::Lost(format!(
+                "Sorry, better luck next time. The words were {}.",
+                self.describe_words()
+            ))
+        } else {
+            let mut summaries =
+                vec![format!(
+                "Your guess was '{}'.",
+                user_input
+            )];
+            summaries.extend(
+                self.describe_boards(),
+            );
+            summaries.push(format!(
+                "You now have {} attempts left.",
+                self.attempts_left()
+            ));
+
+            SessionState::InProgress {
+                summaries,
+            }
+        };
+
+        Ok(MultiSessionState {
+            overall,
+            boards,
+        })
+    }
+
+    /// One line per board: solved, or how the last guess did.
+    pub fn describe_boards(
+        &self,
+    ) -> Vec<String> {
+        self.boards
+            .iter()
+            .enumerate()
+            .map(|(i, board)| {
+                let progress = if board.is_won() {
+                    format!(
+                        "solved in {} guesses",
+                        board.attempts().len()
+                    )
+                } else {
+                    board
+                        .attempts()
+                        .last()
+                        .map(|a| CharResult::display(a))
+                        .unwrap_or_else(|| {
+                            "no guesses yet".to_string()
+                        })
+                };
+
+                format!(
+                    "Board {}: {}.",
+                    i + 1,
+                    progress
+                )
+            })
+            .collect()
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        let mut result = vec![format!(
+            "Welcome to Golem Wordle! Please describe Golem in {} words of {} letters at once.",
+            self.boards.len(),
+            self.word_length()
+        )];
+
+        result.extend(
+            self.describe_boards(),
+        );
+
+        result.push(format!(
+            "You had {} attempts left.",
+            self.attempts_left()
+        ));
+
+        result
+    }
+
+    fn describe_words(&self) -> String {
+        self.boards
+            .iter()
+            .map(|b| {
+                format!(
+                    "'{}'",
+                    b.word()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn check_board_count(
+    board_count: usize,
+) -> AppResult<()> {
+    if !BOARD_COUNTS
+        .contains(&board_count)
+    {
+        bail!(AppError::InvalidGameConfig(
+            "a multi-board game has 2 or 4 boards".to_string()
+        ))
+    }
+
+    Ok(())
+}
+
+fn not_enough_words(
+    board_count: usize,
+) -> AppError {
+    AppError::InvalidGameConfig(format!(
+        "the word list has no {} words of the same length to play",
+        board_count
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::RealClock,
+        word_picker::SeededPicker,
+    };
+    use pretty_assertions::assert_eq;
+
+    const WORDS: [&str; 2] =
+        ["golem", "cloud"];
+
+    fn multi_game_state(
+        max_attempts: usize,
+    ) -> MultiGameState<'static> {
+        MultiGameState::of(
+            &WORDS,
+            GameConfig::new(
+                max_attempts,
+                1,
+                32,
+                false,
+            )
+            .unwrap(),
+            &RealClock,
+        )
+        .unwrap()
+    }
+
+    // fn of()
+    #[test]
+    fn of_should_refuse_three_boards() {
+        let actual =
+            MultiGameState::of(
+                &[
+                    "golem", "cloud",
+                    "spice",
+                ],
+                GameConfig::default(),
+                &RealClock,
+            )
+            .map(|_| ());
+        let expected =
+            AppError::InvalidGameConfig(
+                "a multi-board game has 2 or 4 boards".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn new_with()
+    #[test]
+    fn new_with_should_pick_different_words_of_the_same_length(
+    ) {
+        let actual =
+            MultiGameState::new_with(
+                &mut SeededPicker::new(
+                    7,
+                ),
+                4,
+                GameConfig::default(),
+                &RealClock,
+            )
+            .unwrap();
+
+        let mut words: Vec<_> = actual
+            .boards()
+            .iter()
+            .map(|b| b.word())
+            .collect();
+        words.sort();
+        words.dedup();
+
+        assert_eq!(words.len(), 4);
+        assert!(actual
+            .boards()
+            .iter()
+            .all(|b| {
+                b.word_length()
+                    == actual
+                        .word_length()
+            }));
+    }
+
+    // fn guess()
+    #[test]
+    fn guess_should_only_play_the_boards_not_solved_yet(
+    ) {
+        let mut game =
+            multi_game_state(7);
+
+        let actual = game
+            .guess("golem")
+            .unwrap();
+
+        assert!(matches!(
+            actual.boards(),
+            [
+                Some(SessionState::Won(_)),
+                Some(SessionState::InProgress { .. })
+            ]
+        ));
+        assert_eq!(
+            actual.messages()[1..],
+            [
+                "Board 1: solved in 1 guesses.",
+                "Board 2: ['g' => Absent, 'o' => Present, 'l' => Present, 'e' => Absent, 'm' => Absent].",
+                "You now have 6 attempts left."
+            ]
+        );
+
+        let actual = game
+            .guess("CLOUD")
+            .unwrap();
+
+        assert!(matches!(
+            actual.boards(),
+            [
+                None,
+                Some(
+                    SessionState::Won(
+                        _
+                    )
+                )
+            ]
+        ));
+        assert_eq!(
+            *actual.overall(),
+            SessionState::Won("Well done, you've solved all 2 boards in 2 guesses!".to_string())
+        );
+        assert_eq!(
+            game.boards()[0]
+                .attempts()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn guess_should_lose_once_the_shared_attempts_run_out(
+    ) {
+        let mut game =
+            multi_game_state(2);
+
+        game.guess("golem").unwrap();
+        let actual = game
+            .guess("spice")
+            .unwrap();
+
+        assert_eq!(
+            *actual.overall(),
+            SessionState::Lost("Sorry, better luck next time. The words were 'golem', 'cloud'.".to_string())
+        );
+        assert!(game.is_finished());
+    }
+
+    #[test]
+    fn guess_should_play_nothing_when_the_guess_does_not_fit(
+    ) {
+        let mut game =
+            multi_game_state(7);
+
+        let actual = game
+            .guess("gol")
+            .map(|_| ());
+        let expected =
+            AppError::InvalidGuessLength(5);
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(game.guesses(), 0);
+    }
+}
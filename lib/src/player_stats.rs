@@ -0,0 +1,597 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    clock::format_duration,
+    game_state::GameState,
+};
+use chrono::Duration;
+use error_stack::ResultExt;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Serialize,
+    Deserialize,
+)]
+pub struct PlayerStats {
+    played: usize,
+    won: usize,
+    current_streak: usize,
+    max_streak: usize,
+    guess_distribution: Vec<usize>,
+    #[serde(default)]
+    hinted_wins: usize,
+    #[serde(default)]
+    timed_out: usize,
+    // in whole seconds
+    #[serde(default)]
+    fastest_timed_win: Option<i64>,
+}
+impl PlayerStats {
+    pub(crate) const fn new() -> Self {
+        Self {
+            played: 0,
+            won: 0,
+            current_streak: 0,
+            max_streak: 0,
+            guess_distribution:
+                Vec::new(),
+            hinted_wins: 0,
+            timed_out: 0,
+            fastest_timed_win: None,
+        }
+    }
+
+    /// Counts a finished game. Games still in progress are ignored.
+    pub fn record(
+        &mut self,
+        game_state: &GameState,
+    ) {
+        if !game_state.is_finished() {
+            return;
+        }
+
+        self.count_played(game_state);
+
+        if game_state.is_won() {
+            let guesses = game_state
+                .attempts()
+                .len();
+
+            if let Some(count) = self
+                .guess_distribution
+                .get_mut(guesses - 1)
+            {
+                *count += 1;
+            }
+
+            self.won += 1;
+
+            if !game_state
+                .hints()
+                .is_empty()
+            {
+                self.hinted_wins += 1;
+            }
+
+            if game_state
+                .config()
+                .is_timed()
+            {
+                let seconds =
+                    game_state
+                        .elapsed()
+                        .num_seconds();
+
+                self.fastest_timed_win =
+                    Some(
+                        self.fastest_timed_win
+                            .map_or(
+                                seconds,
+                                |s| s.min(seconds),
+                            ),
+                    );
+            }
+
+            self.current_streak += 1;
+            self.max_streak =
+                self.max_streak.max(
+                    self.current_streak,
+                );
+        } else {
+            if game_state.is_timed_out()
+            {
+                self.timed_out += 1;
+            }
+
+            self.current_streak = 0;
+        }
+    }
+
+    /// Counts a game given up before it was finished as a loss, so
+    /// that abandoning a game does not save a streak.
+    pub fn record_abandoned(
+        &mut self,
+        game_state: &GameState,
+    ) {
+        if game_state.is_finished() {
+            return;
+        }
+
+        self.count_played(game_state);
+        self.current_streak = 0;
+    }
+
+    fn count_played(
+        &mut self,
+        game_state: &GameState,
+    ) {
+        let max_attempts = game_state
+            .config()
+            .max_attempts();
+
+        if self.guess_distribution.len()
+            < max_attempts
+        {
+            self.guess_distribution
+                .resize(
+                    max_attempts,
+                    0,
+                );
+        }
+
+        self.played += 1;
+    }
+
+    pub fn played(&self) -> usize {
+        self.played
+    }
+
+    pub fn won(&self) -> usize {
+        self.won
+    }
+
+    /// Games won after asking for at least one hint.
+    pub fn hinted_wins(&self) -> usize {
+        self.hinted_wins
+    }
+
+    /// Games lost by guessing after the deadline.
+    pub fn timed_out(&self) -> usize {
+        self.timed_out
+    }
+
+    pub fn fastest_timed_win(
+        &self,
+    ) -> Option<Duration> {
+        self.fastest_timed_win
+            .map(Duration::seconds)
+    }
+
+    /// Rounded to the nearest whole percent.
+    pub fn win_percentage(
+        &self,
+    ) -> usize {
+        if self.played == 0 {
+            return 0;
+        }
+
+        (self.won * 100
+            + self.played / 2)
+            / self.played
+    }
+
+    pub fn current_streak(
+        &self,
+    ) -> usize {
+        self.current_streak
+    }
+
+    pub fn max_streak(&self) -> usize {
+        self.max_streak
+    }
+
+    /// Number of games won in 1, 2, 3... guesses.
+    pub fn guess_distribution(
+        &self,
+    ) -> &[usize] {
+        &self.guess_distribution
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        let mut result = vec![
+            format!(
+                "Played: {}",
+                self.played
+            ),
+            format!(
+                "Win %: {}",
+                self.win_percentage()
+            ),
+            format!(
+                "Current streak: {}",
+                self.current_streak
+            ),
+            format!(
+                "Max streak: {}",
+                self.max_streak
+            ),
+        ];
+
+        if self.hinted_wins > 0 {
+            result.push(format!(
+                "Won with hints: {}",
+                self.hinted_wins
+            ));
+        }
+
+        if self.timed_out > 0 {
+            result.push(format!(
+                "Timed out: {}",
+                self.timed_out
+            ));
+        }
+
+        if let Some(time) =
+            self.fastest_timed_win()
+        {
+            result.push(format!(
+                "Fastest timed win: {}",
+                format_duration(time)
+            ));
+        }
+
+        if !self
+            .guess_distribution
+            .is_empty()
+        {
+            result.push(
+                "Guess distribution:"
+                    .to_string(),
+            );
+            result.extend(
+                self.guess_distribution
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(i, count)| {
+                            format!(
+                            "  {}: {}",
+                            i + 1,
+                            count
+                        )
+                        },
+                    ),
+            );
+        }
+
+        result
+    }
+
+    pub fn to_bytes(
+        &self,
+    ) -> AppResult<Vec<u8>> {
+        serde_json::to_vec(self)
+            .change_context(
+                AppError::StatsEncode,
+            )
+    }
+
+    pub fn from_bytes(
+        bytes: &[u8],
+    ) -> AppResult<Self> {
+        serde_json::from_slice(bytes)
+            .change_context(
+                AppError::StatsDecode,
+            )
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> AppResult<()> {
+        let bytes = self.to_bytes()?;
+
+        fs::write(path, bytes)
+            .change_context(
+                AppError::FileWrite(
+                    path.display()
+                        .to_string(),
+                ),
+            )
+    }
+
+    /// Starts from scratch when there is no file yet.
+    pub fn load(
+        path: &Path,
+    ) -> AppResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let bytes = fs::read(path)
+            .change_context(
+                AppError::FileRead(
+                    path.display()
+                        .to_string(),
+                ),
+            )?;
+
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::{
+            tests::TestClock, RealClock,
+        },
+        game_config::GameConfig,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn finished_game(
+        guesses: &[&str],
+    ) -> GameState<'static> {
+        let mut game_state =
+            GameState::of(
+                "golem",
+                GameConfig::new(
+                    3, 1, 32, false,
+                )
+                .unwrap(),
+                &RealClock,
+            );
+
+        for guess in guesses {
+            let attempt =
+                game_state.score(guess);
+            game_state
+                .add_attempt(attempt);
+        }
+
+        game_state
+    }
+
+    fn won_in(
+        guesses: usize,
+    ) -> GameState<'static> {
+        let mut played =
+            vec!["spice"; guesses - 1];
+        played.push("golem");
+
+        finished_game(&played)
+    }
+
+    fn lost() -> GameState<'static> {
+        finished_game(&[
+            "spice", "spice", "spice",
+        ])
+    }
+
+    // fn record()
+    #[test]
+    fn record_should_count_wins_losses_and_streaks(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+
+        for game_state in [
+            won_in(1),
+            won_in(3),
+            lost(),
+            won_in(3),
+        ] {
+            stats.record(&game_state);
+        }
+
+        assert_eq!(stats.played(), 4);
+        assert_eq!(stats.won(), 3);
+        assert_eq!(
+            stats.win_percentage(),
+            75
+        );
+        assert_eq!(
+            stats.current_streak(),
+            1
+        );
+        assert_eq!(
+            stats.max_streak(),
+            2
+        );
+        assert_eq!(
+            stats.guess_distribution(),
+            [1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn record_should_ignore_a_game_in_progress(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+
+        stats.record(&finished_game(
+            &["spice"],
+        ));
+
+        assert_eq!(
+            stats,
+            PlayerStats::default()
+        );
+    }
+
+    // fn record_abandoned()
+    #[test]
+    fn record_abandoned_should_count_a_loss_that_breaks_the_streak(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+
+        stats.record(&won_in(2));
+        stats.record_abandoned(
+            &finished_game(&["spice"]),
+        );
+
+        assert_eq!(stats.played(), 2);
+        assert_eq!(stats.won(), 1);
+        assert_eq!(
+            stats.current_streak(),
+            0
+        );
+        assert_eq!(
+            stats.max_streak(),
+            1
+        );
+
+        stats.record_abandoned(
+            &won_in(1),
+        );
+
+        assert_eq!(stats.played(), 2);
+    }
+
+    #[test]
+    fn record_should_count_wins_helped_by_hints(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+        let mut hinted =
+            finished_game(&[]);
+        hinted.hint().unwrap();
+        let attempt =
+            hinted.score("golem");
+        hinted.add_attempt(attempt);
+
+        stats.record(&hinted);
+        stats.record(&won_in(2));
+
+        assert_eq!(stats.won(), 2);
+        assert_eq!(
+            stats.hinted_wins(),
+            1
+        );
+        assert!(stats
+            .describe()
+            .contains(
+                &"Won with hints: 1"
+                    .to_string()
+            ));
+    }
+
+    #[test]
+    fn record_should_keep_the_fastest_timed_win_and_count_time_outs(
+    ) {
+        let clock =
+            TestClock::default();
+        let config =
+            GameConfig::default()
+                .with_time_limits(
+                    Some(
+                        Duration::minutes(
+                            2,
+                        ),
+                    ),
+                    None,
+                )
+                .unwrap();
+        let mut stats =
+            PlayerStats::default();
+
+        for seconds in [90, 45, 150] {
+            let mut game_state =
+                GameState::of(
+                    "golem",
+                    config.clone(),
+                    &clock,
+                );
+            clock.advance(
+                Duration::seconds(
+                    seconds,
+                ),
+            );
+
+            if game_state
+                .is_past_deadline()
+            {
+                game_state
+                    .time_out("golem");
+            } else {
+                let attempt =
+                    game_state
+                        .score("golem");
+                game_state.add_attempt(
+                    attempt,
+                );
+            }
+
+            stats.record(&game_state);
+        }
+
+        assert_eq!(stats.won(), 2);
+        assert_eq!(
+            stats.timed_out(),
+            1
+        );
+        assert_eq!(
+            stats.fastest_timed_win(),
+            Some(Duration::seconds(45))
+        );
+        assert!(stats
+            .describe()
+            .contains(
+            &"Fastest timed win: 0:45"
+                .to_string()
+        ));
+    }
+
+    // fn describe()
+    #[test]
+    fn describe_should_list_every_statistic(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+        stats.record(&won_in(2));
+        stats.record(&lost());
+
+        let actual = stats.describe();
+        let expected = vec![
+            "Played: 2",
+            "Win %: 50",
+            "Current streak: 0",
+            "Max streak: 1",
+            "Guess distribution:",
+            "  1: 0",
+            "  2: 1",
+            "  3: 0",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn to_bytes(), fn from_bytes()
+    #[test]
+    fn from_bytes_should_decode_what_to_bytes_encoded(
+    ) {
+        let mut stats =
+            PlayerStats::default();
+        stats.record(&won_in(2));
+
+        let bytes =
+            stats.to_bytes().unwrap();
+        let actual =
+            PlayerStats::from_bytes(
+                &bytes,
+            )
+            .unwrap();
+
+        assert_eq!(actual, stats);
+    }
+}
@@ -0,0 +1,707 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    clock::{
+        format_duration, Clock, Gmt,
+    },
+    core::letter_count,
+    game_config::GameConfig,
+    game_state::GameState,
+    session_state::SessionState,
+};
+use chrono::Duration;
+use error_stack::bail;
+
+const OUT_OF_RACE: &str =
+    "You're out of the race. The word will be told once everyone is done.";
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum RacerStatus {
+    Solved,
+    Playing,
+    /// Out of attempts or out of time, or still guessing when
+    /// someone else won.
+    Out,
+}
+
+/// A line of the scoreboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    rank: usize,
+    player: String,
+    status: RacerStatus,
+    guesses: usize,
+    time: Duration,
+}
+impl Standing {
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn player(&self) -> &str {
+        &self.player
+    }
+
+    pub fn status(
+        &self,
+    ) -> RacerStatus {
+        self.status
+    }
+
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn describe(&self) -> String {
+        let progress = match self.status
+        {
+            RacerStatus::Solved => {
+                format!("solved in {} guesses", self.guesses)
+            }
+            RacerStatus::Playing => {
+                format!("still playing, {} guesses so far", self.guesses)
+            }
+            RacerStatus::Out => {
+                format!("out after {} guesses", self.guesses)
+            }
+        };
+
+        format!(
+            "{}. {}: {}, {}.",
+            self.rank,
+            self.player,
+            progress,
+            format_duration(self.time)
+        )
+    }
+}
+
+struct Racer<'a> {
+    name: String,
+    game: GameState<'a>,
+}
+
+/// Several named players guessing the same word, each on their own
+/// board. The first one to guess it wins the race.
+pub struct Race<'a> {
+    id: u64,
+    word: &'a str,
+    config: GameConfig,
+    started_at: Gmt,
+    racers: Vec<Racer<'a>>,
+    winner: Option<usize>,
+}
+impl<'a> Race<'a> {
+    pub(crate) fn of(
+        id: u64,
+        word: &'a str,
+        config: GameConfig,
+        clock: &impl Clock,
+    ) -> Self {
+        Self {
+            id,
+            word,
+            config,
+            started_at: clock.now(),
+            racers: vec![],
+            winner: None,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn started_at(&self) -> Gmt {
+        self.started_at
+    }
+
+    pub fn config(
+        &self,
+    ) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn winner(
+        &self,
+    ) -> Option<&str> {
+        self.winner.map(|i| {
+            self.racers[i].name.as_str()
+        })
+    }
+
+    /// Whether someone guessed the word, or every player is out.
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+            || (!self.racers.is_empty()
+                && self
+                    .racers
+                    .iter()
+                    .all(|r| r.game.is_finished()))
+    }
+
+    /// Whether no player has joined or guessed for longer than the
+    /// duration.
+    pub(crate) fn last_update_older_than(
+        &self,
+        duration: &Duration,
+    ) -> bool {
+        self.racers.iter().all(|r| {
+            r.game
+                .last_update_older_than(
+                    duration,
+                )
+        })
+    }
+
+    pub fn revealed_word(
+        &self,
+    ) -> Option<&'a str> {
+        self.is_over()
+            .then_some(self.word)
+    }
+
+    /// Adds a player with a board of their own, starting their time.
+    pub fn join(
+        &mut self,
+        name: &str,
+        clock: &'a impl Clock,
+    ) -> AppResult<&GameState<'a>> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            bail!(AppError::InvalidPlayerName)
+        }
+
+        if self.is_over() {
+            bail!(AppError::RaceOver)
+        }
+
+        if self
+            .position_of(name)
+            .is_some()
+        {
+            bail!(AppError::PlayerAlreadyInRace(
+                name.to_string()
+            ))
+        }
+
+        self.racers.push(Racer {
+            name: name.to_string(),
+            game: GameState::of(
+                self.word,
+                self.config.clone(),
+                clock,
+            ),
+        });
+
+        Ok(&self.racers
+            [self.racers.len() - 1]
+            .game)
+    }
+
+    /// Plays a guess on the board of the player. The first correct
+    /// guess of the race wins it.
+    pub fn guess(
+        &mut self,
+        name: &str,
+        user_input: &str,
+    ) -> AppResult<SessionState> {
+        if self.is_over() {
+            bail!(AppError::RaceOver)
+        }
+
+        let index =
+            self.index_of(name)?;
+        let game = &mut self.racers
+            [index]
+            .game;
+
+        if game.is_finished() {
+            bail!(AppError::NoGameInProgress)
+        }
+
+        let session_state =
+            SessionState::determined_by(user_input, game)?;
+
+        if matches!(
+            session_state,
+            SessionState::Won(_)
+        ) {
+            self.winner = Some(index);
+        }
+
+        // the word is only told once the race is over
+        Ok(match session_state {
+            SessionState::Lost(_)
+                if !self.is_over() =>
+            {
+                SessionState::Lost(
+                    OUT_OF_RACE
+                        .to_string(),
+                )
+            }
+            SessionState::TimedOut(
+                _,
+            ) if !self.is_over() => {
+                SessionState::TimedOut(
+                    OUT_OF_RACE
+                        .to_string(),
+                )
+            }
+            session_state => {
+                session_state
+            }
+        })
+    }
+
+    pub fn player(
+        &self,
+        name: &str,
+    ) -> AppResult<&GameState<'a>> {
+        self.index_of(name).map(|i| {
+            &self.racers[i].game
+        })
+    }
+
+    /// The players, ranked by how they did, then by the guesses they
+    /// used and the time from joining to their last guess. Guesses
+    /// tell nothing about players out of the race, so they are only
+    /// ranked by that time.
+    pub fn standings(
+        &self,
+    ) -> Vec<Standing> {
+        let mut standings: Vec<_> = self
+            .racers
+            .iter()
+            .map(|r| {
+                let game = &r.game;
+
+                let status = if game.is_won() {
+                    RacerStatus::Solved
+                } else if game.is_finished()
+                    || self.winner.is_some()
+                {
+                    RacerStatus::Out
+                } else {
+                    RacerStatus::Playing
+                };
+
+                Standing {
+                    rank: 0,
+                    player: r.name.clone(),
+                    status,
+                    guesses: game.attempts().len(),
+                    time: game.last_update()
+                        - game.started_at(),
+                }
+            })
+            .collect();
+
+        standings.sort_by_key(|s| {
+            let guesses = match s.status
+            {
+                RacerStatus::Out => 0,
+                _ => s.guesses,
+            };
+
+            (s.status, guesses, s.time)
+        });
+
+        for (i, standing) in standings
+            .iter_mut()
+            .enumerate()
+        {
+            standing.rank = i + 1;
+        }
+
+        standings
+    }
+
+    pub fn describe_standings(
+        &self,
+    ) -> Vec<String> {
+        let mut result = vec![format!(
+            "Race #{} for a {}-letter word, started on {}.",
+            self.id,
+            self.word_length(),
+            self.started_at
+        )];
+
+        match (self.winner(), self.revealed_word()) {
+            (Some(winner), Some(word)) => {
+                result.push(format!(
+                    "{} won the race! The word was '{}'.",
+                    winner, word
+                ))
+            }
+            (None, Some(word)) => result.push(format!(
+                "Nobody guessed the word. The word was '{}'.",
+                word
+            )),
+            _ => {}
+        }
+
+        result.extend(
+            self.standings()
+                .iter()
+                .map(
+                    Standing::describe,
+                ),
+        );
+
+        result
+    }
+
+    pub fn word_length(&self) -> usize {
+        letter_count(self.word)
+    }
+
+    fn position_of(
+        &self,
+        name: &str,
+    ) -> Option<usize> {
+        self.racers.iter().position(
+            |r| r.name == name.trim(),
+        )
+    }
+
+    fn index_of(
+        &self,
+        name: &str,
+    ) -> AppResult<usize> {
+        match self.position_of(name) {
+            Some(index) => Ok(index),
+            None => bail!(AppError::PlayerNotInRace(
+                name.trim().to_string()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::{
+            tests::TestClock, RealClock,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    const WORD: &str = "golem";
+
+    fn race_of(
+        clock: &impl Clock,
+    ) -> Race<'static> {
+        Race::of(
+            1,
+            WORD,
+            GameConfig::default(),
+            clock,
+        )
+    }
+
+    // fn join()
+    #[test]
+    fn join_should_refuse_a_blank_or_taken_name(
+    ) {
+        let mut race =
+            race_of(&RealClock);
+        race.join("alice", &RealClock)
+            .unwrap();
+
+        let actual = race
+            .join(" alice ", &RealClock)
+            .map(|_| ());
+        let expected = AppError::PlayerAlreadyInRace(
+            "alice".to_string(),
+        );
+
+        assert_app_error!(
+            actual, expected
+        );
+
+        let actual = race
+            .join("  ", &RealClock)
+            .map(|_| ());
+        let expected =
+            AppError::InvalidPlayerName;
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn guess()
+    #[test]
+    fn guess_should_give_the_race_to_the_first_player_to_win(
+    ) {
+        let mut race =
+            race_of(&RealClock);
+        race.join("alice", &RealClock)
+            .unwrap();
+        race.join("bob", &RealClock)
+            .unwrap();
+
+        race.guess("alice", "spice")
+            .unwrap();
+        let actual = race
+            .guess("bob", "golem")
+            .unwrap();
+
+        assert!(matches!(
+            actual,
+            SessionState::Won(_)
+        ));
+        assert_eq!(
+            race.winner(),
+            Some("bob")
+        );
+        assert_eq!(
+            race.revealed_word(),
+            Some(WORD)
+        );
+
+        let actual = race
+            .guess("alice", "golem");
+        let expected =
+            AppError::RaceOver;
+
+        assert_app_error!(
+            actual, expected
+        );
+
+        let actual = race
+            .join("carol", &RealClock)
+            .map(|_| ());
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn guess_should_not_tell_the_word_to_a_player_out_of_a_race_still_on(
+    ) {
+        let mut race = Race::of(
+            1,
+            WORD,
+            GameConfig::new(
+                1, 5, 5, false,
+            )
+            .unwrap(),
+            &RealClock,
+        );
+        race.join("alice", &RealClock)
+            .unwrap();
+        race.join("bob", &RealClock)
+            .unwrap();
+
+        let actual = race
+            .guess("alice", "spice")
+            .unwrap();
+        let expected =
+            SessionState::Lost(
+                OUT_OF_RACE.to_string(),
+            );
+
+        assert_eq!(actual, expected);
+        assert!(!race.is_over());
+        assert_eq!(
+            race.revealed_word(),
+            None
+        );
+
+        let actual = race
+            .guess("bob", "cloud")
+            .unwrap();
+
+        assert!(actual
+            .messages()
+            .iter()
+            .any(|m| m.contains(WORD)));
+        assert_eq!(
+            race.revealed_word(),
+            Some(WORD)
+        );
+    }
+
+    #[test]
+    fn guess_should_fail_for_a_player_who_has_not_joined(
+    ) {
+        let mut race =
+            race_of(&RealClock);
+        race.join("alice", &RealClock)
+            .unwrap();
+
+        let actual =
+            race.guess("bob", "golem");
+        let expected =
+            AppError::PlayerNotInRace(
+                "bob".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn standings()
+    #[test]
+    fn standings_should_rank_by_guesses_then_time(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 1, 12, 0,
+        );
+        let mut race = race_of(&clock);
+        race.join("alice", &clock)
+            .unwrap();
+        race.join("bob", &clock)
+            .unwrap();
+        race.join("carol", &clock)
+            .unwrap();
+
+        clock.advance(
+            Duration::seconds(30),
+        );
+        race.guess("alice", "spice")
+            .unwrap();
+        race.guess("bob", "spice")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(10),
+        );
+        race.guess("carol", "spice")
+            .unwrap();
+        race.guess("carol", "cloud")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(5),
+        );
+        race.guess("bob", "golem")
+            .unwrap();
+
+        let actual = race
+            .standings()
+            .iter()
+            .map(Standing::describe)
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "1. bob: solved in 2 guesses, 0:45.",
+            "2. alice: out after 1 guesses, 0:30.",
+            "3. carol: out after 2 guesses, 0:40.",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn standings_should_rank_players_who_are_out_by_time_only(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 1, 12, 0,
+        );
+        let mut race = race_of(&clock);
+        race.join("alice", &clock)
+            .unwrap();
+        race.join("bob", &clock)
+            .unwrap();
+        race.join("carol", &clock)
+            .unwrap();
+
+        clock.advance(
+            Duration::seconds(10),
+        );
+        race.guess("alice", "spice")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(5),
+        );
+        race.guess("alice", "cloud")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(15),
+        );
+        race.guess("carol", "spice")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(5),
+        );
+        race.guess("bob", "golem")
+            .unwrap();
+
+        let actual = race
+            .standings()
+            .iter()
+            .map(Standing::describe)
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "1. bob: solved in 1 guesses, 0:35.",
+            "2. alice: out after 2 guesses, 0:15.",
+            "3. carol: out after 1 guesses, 0:30.",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn standings_should_put_faster_players_first_on_equal_guesses(
+    ) {
+        let clock = TestClock::init(
+            2023, 12, 1, 12, 0,
+        );
+        let mut race = race_of(&clock);
+        race.join("alice", &clock)
+            .unwrap();
+        race.join("bob", &clock)
+            .unwrap();
+
+        clock.advance(
+            Duration::seconds(20),
+        );
+        race.guess("bob", "spice")
+            .unwrap();
+        clock.advance(
+            Duration::seconds(20),
+        );
+        race.guess("alice", "spice")
+            .unwrap();
+
+        let actual: Vec<_> = race
+            .standings()
+            .iter()
+            .map(|s| {
+                (
+                    s.player()
+                        .to_string(),
+                    s.status(),
+                )
+            })
+            .collect();
+        let expected = vec![
+            (
+                "bob".to_string(),
+                RacerStatus::Playing,
+            ),
+            (
+                "alice".to_string(),
+                RacerStatus::Playing,
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     app_error::{AppError, AppResult},
     char_result::CharResult,
-    core::GuessResult,
+    clock::format_duration,
+    core::{
+        is_allowed_guess, letter_count,
+        normalized,
+    },
     game_state::GameState,
 };
 use chrono::Duration;
@@ -22,11 +26,28 @@ pub enum SessionState {
     },
     Won(String),
     Lost(String),
+    TimedOut(String),
 }
 
 impl SessionState {
-    fn won() -> Self {
-        SessionState::Won("Well done, you've guessed the word!".to_string())
+    fn won(
+        hints: usize,
+        time: Option<Duration>,
+    ) -> Self {
+        let msg = match hints {
+            0 => "Well done, you've guessed the word!".to_string(),
+            1 => "Well done, you've guessed the word with 1 hint!".to_string(),
+            n => format!("Well done, you've guessed the word with {} hints!", n),
+        };
+
+        SessionState::Won(match time {
+            Some(time) => format!(
+                "{} Your time: {}.",
+                msg,
+                format_duration(time)
+            ),
+            None => msg,
+        })
     }
 
     fn lost(word: &str) -> Self {
@@ -36,14 +57,70 @@ impl SessionState {
         ))
     }
 
+    fn timed_out(word: &str) -> Self {
+        SessionState::TimedOut(format!(
+            "Time's up! The word was '{}'.",
+            word
+        ))
+    }
+
+    /// What the player is told after the guess.
+    pub fn messages(
+        &self,
+    ) -> Vec<String> {
+        match self {
+            Self::InProgress {
+                summaries,
+            } => summaries.clone(),
+            Self::Won(msg)
+            | Self::Lost(msg)
+            | Self::TimedOut(msg) => {
+                vec![msg.clone()]
+            }
+        }
+    }
+
     pub fn determined_by(
         user_input: &str,
         game_state: &mut GameState,
     ) -> AppResult<Self> {
+        // a late guess ends the game, whatever it is
+        if game_state.is_past_deadline()
+        {
+            game_state
+                .time_out(user_input);
+
+            return Ok(
+                Self::timed_out(
+                    game_state.word(),
+                ),
+            );
+        }
+
+        let user_input =
+            Self::checked_guess(
+                user_input, game_state,
+            )?;
+
+        Ok(Self::after_guess(
+            &user_input,
+            game_state,
+        ))
+    }
+
+    /// The guess in lowercase, once it fits the word and the rules of
+    /// the game. Nothing is played yet.
+    pub(crate) fn checked_guess(
+        user_input: &str,
+        game_state: &GameState,
+    ) -> AppResult<String> {
         let word_length =
             game_state.word_length();
 
-        if user_input.len()
+        let user_input =
+            normalized(user_input);
+
+        if letter_count(&user_input)
             != word_length
         {
             bail!(
@@ -51,42 +128,61 @@ impl SessionState {
             )
         }
 
-        let user_input =
-            user_input.to_lowercase();
+        if user_input
+            != game_state.word()
+            && !is_allowed_guess(
+                &user_input,
+            )
+        {
+            bail!(
+                AppError::UnknownWord(
+                    user_input
+                )
+            )
+        }
+
+        game_state.check_hard_mode(
+            &user_input,
+        )?;
+
+        Ok(user_input)
+    }
+
+    /// Plays a guess that has already passed every check.
+    pub(crate) fn after_guess(
+        user_input: &str,
+        game_state: &mut GameState,
+    ) -> Self {
+        game_state.dodge(user_input);
+
         let the_word =
             game_state.word();
 
-        let result = if user_input
-            == *the_word
-        {
-            Self::won()
+        let attempt = game_state
+            .score(user_input);
+
+        if user_input == the_word {
+            game_state
+                .add_attempt(attempt);
+
+            Self::won(
+                game_state
+                    .hints()
+                    .len(),
+                game_state
+                    .config()
+                    .is_timed()
+                    .then(|| {
+                        game_state
+                            .elapsed()
+                    }),
+            )
         } else {
             let attempts_left =
                 game_state
                     .attempts_left();
 
             if attempts_left > 1 {
-                let attempt: Vec<_> = user_input
-                    .chars()
-                    .enumerate()
-                    .map(|(i, char)| {
-                        let result =
-                            match game_state.find_by(&char) {
-                                Some(set) if set.contains(&i) => {
-                                    GuessResult::Correct
-                                }
-                                Some(_) => {
-                                    GuessResult::Present
-                                }
-                                None => {
-                                    GuessResult::Absent
-                                }
-                        };
-
-                        CharResult::new(char, result)
-                    })
-                    .collect();
-
                 let mut summaries =
                     if game_state.last_update_older_than(
                         &IDLE_TIME
@@ -110,14 +206,25 @@ impl SessionState {
                 summaries.extend(
                     session_summary,
                 );
+                summaries.extend(
+                    game_state
+                        .describe_candidates(),
+                );
+                summaries.extend(
+                    game_state
+                        .describe_time(
+                        ),
+                );
 
                 SessionState::InProgress { summaries }
             } else {
+                game_state.add_attempt(
+                    attempt,
+                );
+
                 Self::lost(the_word)
             }
-        };
-
-        Ok(result)
+        }
     }
 }
 
@@ -129,19 +236,25 @@ mod tests {
         clock::{
             tests::TestClock, RealClock,
         },
+        game_config::{
+            GameConfig, GameMode,
+        },
         game_state::GAME_INSTRUCTION,
+        hard_mode::HardModeRule,
+    };
+    use pretty_assertions::{
+        assert_eq, assert_ne,
     };
-    use pretty_assertions::assert_eq;
 
     const WORD: &str = "golem";
 
     const WORD_LENGTH: usize =
         WORD.len();
 
-    const WRONG_ANSWER: &str = "abcde";
+    const WRONG_ANSWER: &str = "spice";
 
     const PREVIOUS_MOVE: &str =
-        "['a' => Absent, 'b' => Absent, 'c' => Absent, 'd' => Absent, 'e' => Present]";
+        "['s' => Absent, 'p' => Absent, 'i' => Absent, 'c' => Absent, 'e' => Present]";
 
     // fn determine_by()
     #[test]
@@ -149,7 +262,9 @@ mod tests {
     ) {
         let mut game_state =
             GameState::of(
-                WORD, &RealClock,
+                WORD,
+                GameConfig::default(),
+                &RealClock,
             );
 
         let user_input = "";
@@ -166,19 +281,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn determined_by_should_count_letters_rather_than_bytes(
+    ) {
+        let word = "grüße";
+        let mut game_state =
+            GameState::of(
+                word,
+                GameConfig::default(),
+                &RealClock,
+            );
+
+        let actual =
+            SessionState::determined_by(
+                "GRÜßE",
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert_eq!(
+            game_state.word_length(),
+            5
+        );
+        assert_eq!(
+            actual,
+            SessionState::won(0, None)
+        );
+    }
+
+    #[test]
+    fn determined_by_should_take_a_combining_accent_for_the_precomposed_letter(
+    ) {
+        let mut game_state =
+            GameState::of(
+                "niño",
+                GameConfig::default(),
+                &RealClock,
+            );
+
+        let actual =
+            SessionState::determined_by(
+                "NIN\u{303}O",
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            SessionState::won(0, None)
+        );
+    }
+
+    #[test]
+    fn determined_by_should_return_err_without_using_an_attempt_when_user_input_is_not_a_word(
+    ) {
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                &RealClock,
+            );
+        let attempts_left =
+            game_state.attempts_left();
+
+        let user_input = "ABCDE";
+
+        let actual =
+            SessionState::determined_by(
+                user_input,
+                &mut game_state,
+            );
+        let expected =
+            AppError::UnknownWord(
+                "abcde".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(
+            game_state.attempts_left(),
+            attempts_left
+        );
+    }
+
+    #[test]
+    fn determined_by_should_return_err_without_using_an_attempt_when_hard_mode_is_violated(
+    ) {
+        let config = GameConfig::new(
+            6, 1, 32, true,
+        )
+        .unwrap();
+        let mut game_state =
+            GameState::of(
+                WORD, config,
+                &RealClock,
+            );
+
+        SessionState::determined_by(
+            WRONG_ANSWER,
+            &mut game_state,
+        )
+        .unwrap();
+
+        let attempts_left =
+            game_state.attempts_left();
+
+        let actual =
+            SessionState::determined_by(
+                "board",
+                &mut game_state,
+            );
+        let expected =
+            AppError::HardModeViolation(
+                HardModeRule::ReusePresentLetter {
+                    letter: "e".to_string(),
+                    count: 1,
+                },
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(
+            game_state.attempts_left(),
+            attempts_left
+        );
+    }
+
     #[test]
     fn determined_by_should_return_win_when_user_guesses_it_on_first_try(
     ) {
         let mut game_state =
             GameState::of(
-                WORD, &RealClock,
+                WORD,
+                GameConfig::default(),
+                &RealClock,
             );
         let session_state =
             SessionState::determined_by(WORD, &mut game_state).unwrap();
 
         assert_eq!(
             session_state,
-            SessionState::won()
+            SessionState::won(0, None)
+        );
+        assert_eq!(
+            game_state.attempts().len(),
+            1
+        );
+        assert!(game_state.is_won());
+    }
+
+    #[test]
+    fn determined_by_should_flag_a_win_helped_by_hints(
+    ) {
+        let mut game_state =
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                &RealClock,
+            );
+        game_state.hint().unwrap();
+
+        let actual =
+            SessionState::determined_by(
+                WORD,
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            SessionState::Won("Well done, you've guessed the word with 1 hint!".to_string())
+        );
+    }
+
+    #[test]
+    fn determined_by_should_record_the_final_guess_when_the_game_is_lost(
+    ) {
+        let config = GameConfig::new(
+            1, 1, 32, false,
+        )
+        .unwrap();
+        let mut game_state =
+            GameState::of(
+                WORD, config,
+                &RealClock,
+            );
+        let session_state =
+            SessionState::determined_by(WRONG_ANSWER, &mut game_state).unwrap();
+
+        assert_eq!(
+            session_state,
+            SessionState::lost(WORD)
+        );
+        assert_eq!(
+            game_state.attempts().len(),
+            1
+        );
+        assert_eq!(
+            game_state.revealed_word(),
+            Some(WORD)
         );
     }
 
@@ -193,7 +496,9 @@ mod tests {
     ) {
         let mut game_state =
             GameState::of(
-                WORD, &RealClock,
+                WORD,
+                GameConfig::default(),
+                &RealClock,
             );
 
         let attempts = args.last_attempt
@@ -252,7 +557,11 @@ mod tests {
             );
 
         let mut game_state =
-            GameState::of(WORD, &clock);
+            GameState::of(
+                WORD,
+                GameConfig::default(),
+                &clock,
+            );
 
         let attempts = args.last_attempt
             ..=(game_state
@@ -283,6 +592,10 @@ mod tests {
                             .into();
                         i
                     ]);
+                summaries.extend(vec![
+                    "Letters in the word, not yet in place: e.".to_string(),
+                    "Letters not in the word: c, i, p, s.".to_string(),
+                ]);
 
                 minute +=
                     idle_minutes as u32;
@@ -351,6 +664,7 @@ mod tests {
             last_attempt: 1,
             last_answer: WORD,
             expected: SessionState::won(
+                0, None,
             ),
         };
         test_determined_by(&args);
@@ -364,6 +678,7 @@ mod tests {
             last_attempt: 2,
             last_answer: WORD,
             expected: SessionState::won(
+                0, None,
             ),
         };
         test_determined_by(&args);
@@ -382,4 +697,195 @@ mod tests {
         test_determined_by(&args);
         test_determined_by_with_test_clock(&args);
     }
+
+    fn timed_game_state(
+        clock: &TestClock,
+        per_game: Option<Duration>,
+        per_guess: Option<Duration>,
+    ) -> GameState<'_> {
+        let config =
+            GameConfig::default()
+                .with_time_limits(
+                    per_game, per_guess,
+                )
+                .unwrap();
+
+        GameState::of(
+            WORD, config, clock,
+        )
+    }
+
+    #[test]
+    fn determined_by_should_show_the_time_left_in_a_timed_game(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut game_state =
+            timed_game_state(
+                &clock,
+                Some(
+                    Duration::minutes(
+                        5,
+                    ),
+                ),
+                Some(
+                    Duration::minutes(
+                        2,
+                    ),
+                ),
+            );
+
+        clock.advance(
+            Duration::seconds(30),
+        );
+
+        let actual =
+            SessionState::determined_by(
+                WRONG_ANSWER,
+                &mut game_state,
+            )
+            .unwrap();
+
+        // the next guess is due two minutes after this one
+        assert_eq!(
+            actual.messages().last(),
+            Some(
+                &"Time played: 0:30, time left: 2:00."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn determined_by_should_end_the_game_on_a_guess_after_the_deadline(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut game_state =
+            timed_game_state(
+                &clock,
+                None,
+                Some(
+                    Duration::minutes(
+                        1,
+                    ),
+                ),
+            );
+
+        clock.advance(
+            Duration::seconds(30),
+        );
+        SessionState::determined_by(
+            WRONG_ANSWER,
+            &mut game_state,
+        )
+        .unwrap();
+
+        clock.advance(
+            Duration::minutes(2),
+        );
+
+        // even the right word is too late
+        let actual =
+            SessionState::determined_by(
+                WORD,
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            SessionState::timed_out(
+                WORD
+            )
+        );
+        assert!(
+            game_state.is_timed_out()
+        );
+        assert!(
+            game_state.is_finished()
+        );
+        assert_eq!(
+            game_state.attempts().len(),
+            1
+        );
+        assert_eq!(
+            game_state.elapsed(),
+            Duration::seconds(90)
+        );
+    }
+
+    #[test]
+    fn determined_by_should_tell_the_time_of_a_timed_win(
+    ) {
+        let clock =
+            TestClock::default();
+        let mut game_state =
+            timed_game_state(
+                &clock,
+                Some(
+                    Duration::minutes(
+                        3,
+                    ),
+                ),
+                None,
+            );
+
+        clock.advance(
+            Duration::seconds(83),
+        );
+
+        let actual =
+            SessionState::determined_by(
+                WORD,
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert_eq!(
+            actual,
+            SessionState::Won("Well done, you've guessed the word! Your time: 1:23.".to_string())
+        );
+    }
+
+    #[test]
+    fn determined_by_should_dodge_a_right_guess_in_adversarial_mode(
+    ) {
+        let config =
+            GameConfig::default()
+                .with_mode(
+                GameMode::Adversarial,
+            );
+        // one of the 8-letter words of the built-in list
+        let mut game_state =
+            GameState::of(
+                "reliable", config,
+                &RealClock,
+            );
+        let candidates = game_state
+            .candidate_count()
+            .unwrap();
+
+        let actual =
+            SessionState::determined_by(
+                "reliable",
+                &mut game_state,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            actual,
+            SessionState::InProgress { .. }
+        ));
+        assert_ne!(
+            game_state.word(),
+            "reliable"
+        );
+        assert!(
+            game_state
+                .candidate_count()
+                .unwrap()
+                < candidates
+        );
+    }
 }
@@ -0,0 +1,228 @@
+use crate::{
+    char_result::CharResult,
+    core::GuessResult,
+    game_history::{
+        GameOutcome, GameRecord,
+    },
+};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+)]
+pub enum ShareTheme {
+    Light,
+    #[default]
+    Dark,
+    /// Orange and blue instead of green and yellow, for color-blind
+    /// players.
+    HighContrast,
+}
+impl ShareTheme {
+    fn tile(
+        &self,
+        result: &GuessResult,
+    ) -> &'static str {
+        match (self, result) {
+            (
+                Self::HighContrast,
+                GuessResult::Correct,
+            ) => "🟧",
+            (
+                Self::HighContrast,
+                GuessResult::Present,
+            ) => "🟦",
+            (
+                _,
+                GuessResult::Correct,
+            ) => "🟩",
+            (
+                _,
+                GuessResult::Present,
+            ) => "🟨",
+            (
+                Self::Light,
+                GuessResult::Absent,
+            ) => "⬜",
+            (
+                _,
+                GuessResult::Absent,
+            ) => "⬛",
+        }
+    }
+
+    fn row(
+        &self,
+        attempt: &[CharResult],
+    ) -> String {
+        attempt
+            .iter()
+            .map(|r| {
+                self.tile(r.result())
+            })
+            .collect()
+    }
+}
+
+/// The spoiler-free grid players post after a game: a header such as
+/// "Golem Wordle 4/6", a blank line, then one row of tiles per guess.
+pub fn share_grid(
+    record: &GameRecord,
+    theme: ShareTheme,
+) -> Vec<String> {
+    let guesses = match record.outcome()
+    {
+        GameOutcome::Won => record
+            .attempts()
+            .len()
+            .to_string(),
+        GameOutcome::Lost
+        | GameOutcome::TimedOut => {
+            "X".to_string()
+        }
+    };
+    let puzzle = record
+        .puzzle_number()
+        .map(|n| format!("#{n} "))
+        .unwrap_or_default();
+
+    let mut result = vec![
+        format!(
+            "Golem Wordle {}{}/{}",
+            puzzle,
+            guesses,
+            record
+                .config()
+                .max_attempts()
+        ),
+        String::new(),
+    ];
+
+    result.extend(
+        record
+            .attempts()
+            .iter()
+            .map(|a| theme.row(a)),
+    );
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::RealClock,
+        game_config::GameConfig,
+        game_history::GameHistory,
+        game_state::GameState,
+    };
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    fn record_of(
+        guesses: &[&str],
+        max_attempts: usize,
+        puzzle_number: Option<usize>,
+    ) -> GameRecord {
+        let mut game_state =
+            GameState::of(
+                "golem",
+                GameConfig::new(
+                    max_attempts,
+                    1,
+                    32,
+                    false,
+                )
+                .unwrap(),
+                &RealClock,
+            );
+
+        if let Some(number) =
+            puzzle_number
+        {
+            game_state = game_state
+                .with_puzzle_number(
+                    number,
+                );
+        }
+
+        for guess in guesses {
+            let attempt =
+                game_state.score(guess);
+            game_state
+                .add_attempt(attempt);
+        }
+
+        let mut history =
+            GameHistory::default();
+        let id = history
+            .record(1, &game_state)
+            .unwrap();
+
+        history
+            .game(id)
+            .unwrap()
+            .clone()
+    }
+
+    // fn share_grid()
+    #[test_case(
+        ShareTheme::Dark,
+        &["⬛⬛⬛⬛🟨", "🟩🟩🟩🟩🟩"] ;
+        "dark theme"
+    )]
+    #[test_case(
+        ShareTheme::Light,
+        &["⬜⬜⬜⬜🟨", "🟩🟩🟩🟩🟩"] ;
+        "light theme"
+    )]
+    #[test_case(
+        ShareTheme::HighContrast,
+        &["⬛⬛⬛⬛🟦", "🟧🟧🟧🟧🟧"] ;
+        "high contrast theme"
+    )]
+    fn share_grid_should_draw_one_row_per_guess(
+        theme: ShareTheme,
+        rows: &[&str],
+    ) {
+        let record = record_of(
+            &["spice", "golem"],
+            5,
+            None,
+        );
+
+        let actual =
+            share_grid(&record, theme);
+        let mut expected = vec![
+            "Golem Wordle 2/5",
+            "",
+        ];
+        expected.extend(rows);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn share_grid_should_mark_a_lost_daily_puzzle(
+    ) {
+        let record = record_of(
+            &["spice", "spice"],
+            2,
+            Some(115),
+        );
+
+        let actual = share_grid(
+            &record,
+            ShareTheme::Dark,
+        );
+
+        assert_eq!(
+            actual[0],
+            "Golem Wordle #115 X/2"
+        );
+    }
+}
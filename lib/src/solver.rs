@@ -0,0 +1,499 @@
+use crate::{
+    char_result::CharResult,
+    core::{
+        char_map_from, letter_count,
+        score_guess, CharMap,
+        GuessResult,
+    },
+    word_list::with_words,
+};
+use std::{
+    cmp::Ordering, collections::HashMap,
+};
+
+/// Number of next guesses shown to the player.
+pub const SUGGESTION_COUNT: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    word: &'static str,
+    entropy: f64,
+    is_candidate: bool,
+}
+impl Suggestion {
+    pub fn word(&self) -> &'static str {
+        self.word
+    }
+
+    /// Expected information of the guess, in bits.
+    pub fn entropy(&self) -> f64 {
+        self.entropy
+    }
+
+    /// Whether the guess could still be the word.
+    pub fn is_candidate(&self) -> bool {
+        self.is_candidate
+    }
+
+    fn cmp_rank(
+        &self,
+        other: &Self,
+    ) -> Ordering {
+        other
+            .entropy
+            .partial_cmp(&self.entropy)
+            .unwrap_or(Ordering::Equal)
+            .then(
+                other.is_candidate.cmp(
+                    &self.is_candidate,
+                ),
+            )
+            .then(
+                self.word
+                    .cmp(other.word),
+            )
+    }
+}
+
+/// Narrows down the words of the word list that fit the guesses so
+/// far, and ranks the next guess by how much it is expected to tell.
+pub struct Solver {
+    pool: Vec<(&'static str, CharMap)>,
+}
+impl Solver {
+    /// Uses the words of the current word list with this many letters.
+    pub fn new(
+        word_length: usize,
+    ) -> Self {
+        with_words(|words| {
+            Self::from_words(
+                words,
+                word_length,
+            )
+        })
+    }
+
+    pub fn from_words(
+        words: &[&'static str],
+        word_length: usize,
+    ) -> Self {
+        let pool = words
+            .iter()
+            .filter(|w| {
+                letter_count(w)
+                    == word_length
+            })
+            .map(|w| {
+                (*w, char_map_from(w))
+            })
+            .collect();
+
+        Self { pool }
+    }
+
+    pub fn pool(
+        &self,
+    ) -> impl Iterator<Item = &'static str>
+           + '_ {
+        self.pool
+            .iter()
+            .map(|(w, _)| *w)
+    }
+
+    /// The words that would have scored every attempt the same way.
+    pub fn candidates(
+        &self,
+        attempts: &[Vec<CharResult>],
+    ) -> Vec<&'static str> {
+        self.candidate_maps(attempts)
+            .into_iter()
+            .map(|(w, _)| w)
+            .collect()
+    }
+
+    fn candidate_maps(
+        &self,
+        attempts: &[Vec<CharResult>],
+    ) -> Vec<(&'static str, &CharMap)>
+    {
+        self.pool
+            .iter()
+            .filter(|(_, char_map)| {
+                attempts.iter().all(
+                    |attempt| {
+                        score_guess(
+                            &guess_of(
+                                attempt,
+                            ),
+                            char_map,
+                        ) == *attempt
+                    },
+                )
+            })
+            .map(|(w, char_map)| {
+                (*w, char_map)
+            })
+            .collect()
+    }
+
+    /// Every word of the pool as the next guess, best first.
+    pub fn rank(
+        &self,
+        attempts: &[Vec<CharResult>],
+    ) -> Vec<Suggestion> {
+        let candidates = self
+            .candidate_maps(attempts);
+
+        if candidates.is_empty() {
+            return vec![];
+        }
+
+        let mut result: Vec<_> = self
+            .pool()
+            .map(|guess| {
+                Suggestion {
+                word: guess,
+                entropy: entropy_of(
+                    guess,
+                    &candidates,
+                ),
+                is_candidate: candidates
+                    .iter()
+                    .any(|(w, _)| {
+                        *w == guess
+                    }),
+            }
+            })
+            .collect();
+
+        result.sort_by(|a, b| {
+            a.cmp_rank(b)
+        });
+
+        result
+    }
+
+    pub fn suggest(
+        &self,
+        attempts: &[Vec<CharResult>],
+    ) -> Option<Suggestion> {
+        self.rank(attempts)
+            .into_iter()
+            .next()
+    }
+
+    pub fn describe(
+        &self,
+        attempts: &[Vec<CharResult>],
+    ) -> Vec<String> {
+        let candidates =
+            self.candidates(attempts);
+
+        let mut result = vec![
+            match candidates.as_slice() {
+                [] => "No word of the word list fits your guesses.".to_string(),
+                [word] => format!("Only '{}' fits your guesses.", word),
+                _ => format!("{} words still fit your guesses.", candidates.len()),
+            },
+        ];
+
+        result.extend(
+            self.rank(attempts)
+                .iter()
+                .filter(|s| {
+                    s.is_candidate
+                        || s.entropy > 0.0
+                })
+                .take(SUGGESTION_COUNT)
+                .map(|s| {
+                    format!(
+                        "Try '{}': {:.2} bits of information{}.",
+                        s.word,
+                        s.entropy,
+                        if s.is_candidate {
+                            ", and it could be the word"
+                        } else {
+                            ""
+                        }
+                    )
+                }),
+        );
+
+        result
+    }
+
+    /// Plays the word by always taking the best suggestion, answering
+    /// the guesses made, or `None` when the word is not in the pool.
+    pub fn solve(
+        &self,
+        word: &str,
+    ) -> Option<Vec<&'static str>> {
+        let char_map =
+            char_map_from(word);
+        let mut attempts = vec![];
+        let mut guesses = vec![];
+
+        while let Some(suggestion) =
+            self.suggest(&attempts)
+        {
+            let guess = suggestion.word;
+            guesses.push(guess);
+
+            if guess == word {
+                return Some(guesses);
+            }
+
+            attempts.push(score_guess(
+                guess, &char_map,
+            ));
+        }
+
+        None
+    }
+
+    /// Mean number of guesses `solve` needs over the whole pool.
+    pub fn average_guesses(
+        &self,
+    ) -> Option<f64> {
+        let counts: Option<Vec<_>> =
+            self.pool()
+                .map(|w| {
+                    self.solve(w).map(
+                        |g| g.len(),
+                    )
+                })
+                .collect();
+        let counts = counts?;
+
+        if counts.is_empty() {
+            return None;
+        }
+
+        Some(
+            counts.iter().sum::<usize>()
+                as f64
+                / counts.len() as f64,
+        )
+    }
+}
+
+fn guess_of(
+    attempt: &[CharResult],
+) -> String {
+    attempt
+        .iter()
+        .map(|r| r.letter())
+        .collect()
+}
+
+/// How evenly the guess splits the candidates by the feedback each
+/// of them would give.
+fn entropy_of(
+    guess: &str,
+    candidates: &[(&str, &CharMap)],
+) -> f64 {
+    let mut patterns: HashMap<
+        Vec<u8>,
+        usize,
+    > = HashMap::new();
+
+    for (_, char_map) in candidates {
+        let pattern = score_guess(
+            guess, char_map,
+        )
+        .iter()
+        .map(|r| match r.result() {
+            GuessResult::Correct => 2,
+            GuessResult::Present => 1,
+            GuessResult::Absent => 0,
+        })
+        .collect();
+
+        *patterns
+            .entry(pattern)
+            .or_default() += 1;
+    }
+
+    let total = candidates.len() as f64;
+
+    patterns
+        .values()
+        .map(|count| {
+            let p =
+                *count as f64 / total;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const WORDS: &[&str] = &[
+        "golem", "globe", "gloom",
+        "lemon", "melon", "spice",
+    ];
+
+    fn solver() -> Solver {
+        Solver::from_words(WORDS, 5)
+    }
+
+    fn attempt(
+        guess: &str,
+        word: &str,
+    ) -> Vec<CharResult> {
+        score_guess(
+            guess,
+            &char_map_from(word),
+        )
+    }
+
+    // fn candidates()
+    #[test]
+    fn candidates_should_keep_only_words_that_fit_every_attempt(
+    ) {
+        let actual = solver()
+            .candidates(&[attempt(
+                "spice", "golem",
+            )]);
+
+        assert_eq!(
+            actual,
+            vec![
+                "golem", "lemon",
+                "melon"
+            ]
+        );
+    }
+
+    #[test]
+    fn candidates_should_be_the_whole_pool_before_the_first_guess(
+    ) {
+        let actual =
+            solver().candidates(&[]);
+
+        assert_eq!(actual, WORDS);
+    }
+
+    // fn rank()
+    #[test]
+    fn rank_should_prefer_a_guess_that_splits_the_candidates(
+    ) {
+        let solver = solver();
+        let attempts =
+            [attempt("spice", "golem")];
+
+        let ranked =
+            solver.rank(&attempts);
+
+        assert_eq!(
+            ranked.len(),
+            WORDS.len()
+        );
+        assert!(ranked
+            .windows(2)
+            .all(|w| w[0].entropy()
+                >= w[1].entropy()));
+        // "spice" was already played and tells nothing new
+        assert_eq!(
+            ranked
+                .last()
+                .unwrap()
+                .word(),
+            "spice"
+        );
+        assert_eq!(
+            ranked
+                .last()
+                .unwrap()
+                .entropy(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn rank_should_be_empty_when_no_word_fits(
+    ) {
+        let actual =
+            solver().rank(&[attempt(
+                "golem", "zzzzz",
+            )]);
+
+        assert!(actual.is_empty());
+    }
+
+    // fn suggest()
+    #[test]
+    fn suggest_should_name_the_word_once_it_is_the_only_candidate(
+    ) {
+        let actual = solver()
+            .suggest(&[attempt(
+                "lemon", "melon",
+            )])
+            .unwrap();
+
+        assert_eq!(
+            actual.word(),
+            "melon"
+        );
+        assert!(actual.is_candidate());
+    }
+
+    // fn describe()
+    #[test]
+    fn describe_should_count_the_candidates_and_rank_the_best_guesses(
+    ) {
+        let actual =
+            solver().describe(&[
+                attempt(
+                    "lemon", "melon",
+                ),
+            ]);
+        let expected = vec![
+            "Only 'melon' fits your guesses.",
+            "Try 'melon': 0.00 bits of information, and it could be the word.",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn solve(), fn average_guesses()
+    #[test]
+    fn solve_should_find_every_word_of_the_pool(
+    ) {
+        let solver = solver();
+
+        for word in WORDS {
+            let guesses = solver
+                .solve(word)
+                .unwrap();
+
+            assert_eq!(
+                guesses.last(),
+                Some(word)
+            );
+            assert!(
+                guesses.len() <= 3,
+                "{word}: {guesses:?}"
+            );
+        }
+
+        assert!(
+            solver
+                .average_guesses()
+                .unwrap()
+                <= 3.0
+        );
+    }
+
+    #[test]
+    fn solve_should_give_up_on_a_word_outside_the_pool(
+    ) {
+        assert_eq!(
+            solver().solve("zzzzz"),
+            None
+        );
+    }
+}
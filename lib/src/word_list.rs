@@ -0,0 +1,581 @@
+use crate::{
+    app_error::{
+        AppError, AppResult,
+        AppResultExt,
+    },
+    core::{
+        load_words, words_from,
+        BUILT_IN_WORDS,
+    },
+};
+use error_stack::{Report, ResultExt};
+use once_cell::sync::Lazy;
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    env, fs,
+    path::PathBuf,
+    sync::{
+        Mutex, PoisonError, RwLock,
+    },
+};
+
+pub const WORDS_FILE_ENV_VAR: &str =
+    "GOLEM_WORDLE_WORDS_FILE";
+
+pub const WORDS_ENV_VAR: &str =
+    "GOLEM_WORDLE_WORDS";
+
+#[allow(clippy::unwrap_used)]
+static BUILT_IN: Lazy<WordList> =
+    Lazy::new(|| {
+        load_words()
+            .map(WordList::leaked)
+            .err_as_string()
+            .unwrap()
+    });
+
+static CURRENT: Lazy<CurrentList> =
+    Lazy::new(|| {
+        CurrentList::from_source(
+            &WordListSource::from_env(),
+        )
+    });
+
+/// Every word leaked so far, so that installing a list again reuses
+/// the words it shares with the lists installed before.
+static INTERNED: Lazy<
+    Mutex<HashSet<&'static str>>,
+> = Lazy::new(|| {
+    Mutex::new(HashSet::new())
+});
+
+/// Fails with the error of the list named by the environment, the
+/// first time only, as the compiled-in list is played in its place.
+/// Answers the number of words in play otherwise.
+pub fn check_env() -> AppResult<usize> {
+    CURRENT.check()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WordListSource {
+    BuiltIn,
+    File(PathBuf),
+    Text(String),
+}
+impl WordListSource {
+    pub fn from_env() -> Self {
+        Self::from_vars(
+            env::var(
+                WORDS_FILE_ENV_VAR,
+            )
+            .ok(),
+            env::var(WORDS_ENV_VAR)
+                .ok(),
+        )
+    }
+
+    fn from_vars(
+        words_file: Option<String>,
+        words: Option<String>,
+    ) -> Self {
+        match (words_file, words) {
+            (Some(path), _) => {
+                Self::File(path.into())
+            }
+            (None, Some(text)) => {
+                Self::Text(text)
+            }
+            (None, None) => {
+                Self::BuiltIn
+            }
+        }
+    }
+
+    pub fn bytes(
+        &self,
+    ) -> AppResult<Cow<'_, [u8]>> {
+        match self {
+            Self::BuiltIn => Ok(
+                Cow::Borrowed(
+                    BUILT_IN_WORDS,
+                ),
+            ),
+            Self::File(path) => fs::read(
+                path,
+            )
+            .map(Cow::Owned)
+            .change_context(
+                AppError::FileRead(
+                    path.display()
+                        .to_string(),
+                ),
+            ),
+            Self::Text(text) => Ok(
+                Cow::Borrowed(
+                    text.as_bytes(),
+                ),
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordList(Vec<&'static str>);
+impl WordList {
+    pub fn built_in() -> Self {
+        BUILT_IN.clone()
+    }
+
+    pub fn from_bytes(
+        bytes: &[u8],
+    ) -> AppResult<Self> {
+        words_from(bytes)
+            .map(Self::leaked)
+    }
+
+    pub fn load(
+        source: &WordListSource,
+    ) -> AppResult<Self> {
+        match source {
+            WordListSource::BuiltIn => {
+                Ok(Self::built_in())
+            }
+            source => Self::from_bytes(
+                &source.bytes()?,
+            ),
+        }
+    }
+
+    pub fn words(
+        &self,
+    ) -> &[&'static str] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Games borrow their word for as long as they last, so words
+    // outlive the list they came from when a new list is installed.
+    // A word is only leaked the first time it is seen.
+    fn leaked(
+        words: Vec<String>,
+    ) -> Self {
+        let mut interned = INTERNED
+            .lock()
+            .unwrap_or_else(
+                PoisonError::into_inner,
+            );
+
+        Self(
+            words
+                .into_iter()
+                .map(|w| {
+                    match interned
+                        .get(w.as_str())
+                    {
+                        Some(word) => *word,
+                        None => {
+                            let word: &'static str =
+                                Box::leak(
+                                    w.into_boxed_str(),
+                                );
+                            interned
+                                .insert(word);
+
+                            word
+                        }
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The words in play. When the source it is made from cannot be
+/// loaded, the compiled-in list is played, and the error is kept to
+/// be reported once rather than stopping the worker.
+struct CurrentList {
+    list: RwLock<WordList>,
+    error:
+        Mutex<Option<Report<AppError>>>,
+}
+impl CurrentList {
+    fn from_source(
+        source: &WordListSource,
+    ) -> Self {
+        let (list, error) =
+            match WordList::load(source)
+            {
+                Ok(list) => {
+                    (list, None)
+                }
+                Err(error) => (
+                    WordList::built_in(
+                    ),
+                    Some(error),
+                ),
+            };
+
+        Self {
+            list: RwLock::new(list),
+            error: Mutex::new(error),
+        }
+    }
+
+    fn check(
+        &self,
+    ) -> AppResult<usize> {
+        let error = self
+            .error
+            .lock()
+            .unwrap_or_else(
+                PoisonError::into_inner,
+            )
+            .take();
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(self
+                .with_words(|words| {
+                    words.len()
+                })),
+        }
+    }
+
+    fn install(&self, list: WordList) {
+        *self
+            .list
+            .write()
+            .unwrap_or_else(
+                PoisonError::into_inner,
+            ) = list;
+    }
+
+    fn install_from(
+        &self,
+        source: &WordListSource,
+    ) -> AppResult<usize> {
+        let list =
+            WordList::load(source)?;
+        let count = list.len();
+
+        self.install(list);
+
+        Ok(count)
+    }
+
+    fn with_words<T>(
+        &self,
+        f: impl FnOnce(&[&'static str]) -> T,
+    ) -> T {
+        let list = self
+            .list
+            .read()
+            .unwrap_or_else(
+                PoisonError::into_inner,
+            );
+
+        f(list.words())
+    }
+}
+
+pub fn install(list: WordList) {
+    CURRENT.install(list)
+}
+
+pub fn install_from(
+    source: &WordListSource,
+) -> AppResult<usize> {
+    CURRENT.install_from(source)
+}
+
+pub(crate) fn with_words<T>(
+    f: impl FnOnce(&[&'static str]) -> T,
+) -> T {
+    CURRENT.with_words(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_app_error;
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    // fn from_vars()
+    #[test_case(
+        Some("words.txt"), Some("golem"),
+        WordListSource::File("words.txt".into()) ;
+        "the words file wins over the words"
+    )]
+    #[test_case(
+        None, Some("golem"),
+        WordListSource::Text("golem".to_string()) ;
+        "the words are used when no file is given"
+    )]
+    #[test_case(
+        None, None,
+        WordListSource::BuiltIn ;
+        "the compiled-in list is the fallback"
+    )]
+    fn from_vars_should_pick_the_word_list_source(
+        words_file: Option<&str>,
+        words: Option<&str>,
+        expected: WordListSource,
+    ) {
+        let actual =
+            WordListSource::from_vars(
+                words_file.map(
+                    str::to_string,
+                ),
+                words.map(
+                    str::to_string,
+                ),
+            );
+
+        assert_eq!(actual, expected);
+    }
+
+    // fn load()
+    #[test]
+    fn load_should_read_the_compiled_in_list(
+    ) {
+        let actual = WordList::load(
+            &WordListSource::BuiltIn,
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.words(),
+            load_words().unwrap()
+        );
+    }
+
+    #[test]
+    fn load_should_read_words_from_text(
+    ) {
+        let actual = WordList::load(
+            &WordListSource::Text(
+                "Golem  is\ninvincible"
+                    .to_string(),
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.words(),
+            [
+                "golem",
+                "is",
+                "invincible"
+            ]
+        );
+    }
+
+    #[test]
+    fn load_should_read_words_from_a_file(
+    ) {
+        let path =
+            env::temp_dir().join(
+                "golem-wordle-word-list-test.txt",
+            );
+        fs::write(&path, "golem wasm")
+            .unwrap();
+
+        let actual = WordList::load(
+            &WordListSource::File(
+                path.clone(),
+            ),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            actual.unwrap().words(),
+            ["golem", "wasm"]
+        );
+    }
+
+    #[test]
+    fn load_should_fail_when_the_file_cannot_be_read(
+    ) {
+        let path = env::temp_dir()
+            .join("golem-wordle-missing-word-list.txt");
+
+        let actual = WordList::load(
+            &WordListSource::File(
+                path.clone(),
+            ),
+        );
+        let expected =
+            AppError::FileRead(
+                path.display()
+                    .to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn from_bytes()
+    #[test]
+    fn from_bytes_should_reuse_the_words_it_has_seen(
+    ) {
+        let first =
+            WordList::from_bytes(
+                b"golem invincible",
+            )
+            .unwrap();
+        let second =
+            WordList::from_bytes(
+                b"invincible wasm",
+            )
+            .unwrap();
+
+        assert_eq!(
+            first.words()[1].as_ptr(),
+            second.words()[0].as_ptr()
+        );
+    }
+
+    // fn check()
+    #[test]
+    fn check_should_report_a_broken_source_once_and_play_the_compiled_in_list(
+    ) {
+        let current =
+            CurrentList::from_source(
+                &WordListSource::Text(
+                    " ".to_string(),
+                ),
+            );
+
+        let actual = current.check();
+        let expected =
+            AppError::NoWords;
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(
+            current.check().unwrap(),
+            WordList::built_in().len()
+        );
+        assert_eq!(
+            current.with_words(
+                |words| words.to_vec()
+            ),
+            WordList::built_in()
+                .words()
+        );
+    }
+
+    // fn install()
+    #[test]
+    fn install_should_replace_the_words_in_play(
+    ) {
+        let current =
+            CurrentList::from_source(
+                &WordListSource::BuiltIn,
+            );
+
+        current.install(
+            WordList::from_bytes(
+                b"golem wasm",
+            )
+            .unwrap(),
+        );
+
+        let actual = current
+            .with_words(|words| {
+                words.to_vec()
+            });
+
+        assert_eq!(
+            actual,
+            ["golem", "wasm"]
+        );
+    }
+
+    // fn install_from()
+    #[test]
+    fn install_from_should_answer_the_number_of_words_installed(
+    ) {
+        let current =
+            CurrentList::from_source(
+                &WordListSource::BuiltIn,
+            );
+
+        let actual = current
+            .install_from(
+                &WordListSource::Text(
+                    "golem is invincible"
+                        .to_string(),
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(actual, 3);
+        assert_eq!(
+            current.with_words(
+                |words| words.to_vec()
+            ),
+            [
+                "golem",
+                "is",
+                "invincible"
+            ]
+        );
+    }
+
+    #[test]
+    fn install_from_should_keep_the_words_in_play_when_the_source_is_broken(
+    ) {
+        let current =
+            CurrentList::from_source(
+                &WordListSource::BuiltIn,
+            );
+
+        let actual = current
+            .install_from(
+                &WordListSource::Text(
+                    " ".to_string(),
+                ),
+            );
+        let expected =
+            AppError::NoWords;
+
+        assert_app_error!(
+            actual, expected
+        );
+        assert_eq!(
+            current.with_words(
+                |words| words.to_vec()
+            ),
+            WordList::built_in()
+                .words()
+        );
+    }
+
+    #[test]
+    fn load_should_fail_when_the_text_contains_no_words(
+    ) {
+        let actual = WordList::load(
+            &WordListSource::Text(
+                "  \n ".to_string(),
+            ),
+        );
+        let expected =
+            AppError::NoWords;
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    core::{
+        entries_from, letter_count,
+        letters_of, text_from,
+    },
+};
+use error_stack::bail;
+use std::collections::{
+    BTreeMap, HashSet,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordListReport {
+    entry_count: usize,
+    accepted: Vec<String>,
+    mixed_case: Vec<String>,
+    non_ascii: Vec<String>,
+    invalid: Vec<String>,
+    duplicates: Vec<String>,
+    rejected: Vec<String>,
+    length_distribution:
+        BTreeMap<usize, usize>,
+}
+impl WordListReport {
+    pub fn of(
+        bytes: &[u8],
+    ) -> AppResult<Self> {
+        let mut report = Self {
+            entry_count: 0,
+            accepted: vec![],
+            mixed_case: vec![],
+            non_ascii: vec![],
+            invalid: vec![],
+            duplicates: vec![],
+            rejected: vec![],
+            length_distribution:
+                BTreeMap::new(),
+        };
+        let mut seen = HashSet::new();
+
+        for entry in entries_from(
+            text_from(bytes)?,
+        ) {
+            report.entry_count += 1;
+
+            let word =
+                entry.to_lowercase();
+
+            if word != entry {
+                report.mixed_case.push(
+                    entry.to_string(),
+                );
+            }
+
+            if !word.is_ascii() {
+                report.non_ascii.push(
+                    entry.to_string(),
+                );
+            }
+
+            if !is_made_of_letters(
+                &word,
+            ) {
+                report.invalid.push(
+                    entry.to_string(),
+                );
+                report.rejected.push(
+                    entry.to_string(),
+                );
+            } else if !seen
+                .insert(word.clone())
+            {
+                report.duplicates.push(
+                    entry.to_string(),
+                );
+                report.rejected.push(
+                    entry.to_string(),
+                );
+            } else {
+                *report
+                    .length_distribution
+                    .entry(
+                        letter_count(
+                            &word,
+                        ),
+                    )
+                    .or_default() += 1;
+                report
+                    .accepted
+                    .push(word);
+            }
+        }
+
+        Ok(report)
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entry_count
+    }
+
+    /// Unique, lowercase words made of letters only.
+    pub fn accepted(
+        &self,
+    ) -> &[String] {
+        &self.accepted
+    }
+
+    pub fn mixed_case(
+        &self,
+    ) -> &[String] {
+        &self.mixed_case
+    }
+
+    /// Entries outside ASCII. They are playable, but worth a look
+    /// when the list is meant to be English.
+    pub fn non_ascii(
+        &self,
+    ) -> &[String] {
+        &self.non_ascii
+    }
+
+    /// Entries with characters other than letters.
+    pub fn invalid(&self) -> &[String] {
+        &self.invalid
+    }
+
+    pub fn duplicates(
+        &self,
+    ) -> &[String] {
+        &self.duplicates
+    }
+
+    /// Entries left out of `accepted`, in the order they appear.
+    pub fn rejected(
+        &self,
+    ) -> &[String] {
+        &self.rejected
+    }
+
+    /// Number of accepted words per word length.
+    pub fn length_distribution(
+        &self,
+    ) -> &BTreeMap<usize, usize> {
+        &self.length_distribution
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.mixed_case.is_empty()
+            && self.rejected.is_empty()
+    }
+
+    /// Turns the first problem found into an error.
+    pub fn strict(
+        &self,
+    ) -> AppResult<()> {
+        if let Some(word) =
+            self.mixed_case.first()
+        {
+            bail!(
+                AppError::MixedCaseWord(
+                    word.clone()
+                )
+            )
+        }
+
+        if let Some(word) =
+            self.invalid.first()
+        {
+            bail!(
+                AppError::InvalidWord(
+                    word.clone()
+                )
+            )
+        }
+
+        if let Some(word) =
+            self.duplicates.first()
+        {
+            bail!(
+                AppError::DuplicateWord(
+                    word.clone()
+                )
+            )
+        }
+
+        if self.accepted.is_empty() {
+            bail!(AppError::NoWords)
+        }
+
+        Ok(())
+    }
+
+    pub fn describe(
+        &self,
+    ) -> Vec<String> {
+        let mut result = vec![format!(
+            "Checked {} entries: {} accepted, {} rejected.",
+            self.entry_count,
+            self.accepted.len(),
+            self.rejected.len()
+        )];
+
+        for (label, entries) in [
+            (
+                "Mixed case",
+                &self.mixed_case,
+            ),
+            (
+                "Not ASCII",
+                &self.non_ascii,
+            ),
+            (
+                "Not letters",
+                &self.invalid,
+            ),
+            (
+                "Duplicates",
+                &self.duplicates,
+            ),
+        ] {
+            if !entries.is_empty() {
+                result.push(format!(
+                    "{} ({}): {}",
+                    label,
+                    entries.len(),
+                    entries.join(", ")
+                ));
+            }
+        }
+
+        result.push(
+            "Word lengths:".to_string(),
+        );
+        result.extend(
+            self.length_distribution
+                .iter()
+                .map(|(length, count)| {
+                    format!(
+                        "  {:>2} letters: {}",
+                        length, count
+                    )
+                }),
+        );
+
+        result
+    }
+}
+
+// a letter may carry combining marks, but has to start with a letter
+fn is_made_of_letters(
+    word: &str,
+) -> bool {
+    letters_of(word).iter().all(|l| {
+        l.chars().next().is_some_and(
+            char::is_alphabetic,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_app_error;
+    use pretty_assertions::assert_eq;
+    use test_case::test_case;
+
+    const WORDS: &str = "golem CloudNative wasm\ngolem web-assembly Wasm café 42";
+
+    fn report() -> WordListReport {
+        WordListReport::of(
+            WORDS.as_bytes(),
+        )
+        .unwrap()
+    }
+
+    // fn of()
+    #[test]
+    fn of_should_list_every_problem_found_in_the_word_list(
+    ) {
+        let report = report();
+
+        assert_eq!(
+            report.entry_count(),
+            8
+        );
+        assert_eq!(
+            report.accepted(),
+            [
+                "golem",
+                "cloudnative",
+                "wasm",
+                "café"
+            ]
+        );
+        assert_eq!(
+            report.mixed_case(),
+            ["CloudNative", "Wasm"]
+        );
+        assert_eq!(
+            report.non_ascii(),
+            ["café"]
+        );
+        assert_eq!(
+            report.invalid(),
+            ["web-assembly", "42"]
+        );
+        assert_eq!(
+            report.duplicates(),
+            ["golem", "Wasm"]
+        );
+        assert_eq!(
+            report.rejected(),
+            [
+                "golem",
+                "web-assembly",
+                "Wasm",
+                "42"
+            ]
+        );
+        assert_eq!(
+            report
+                .length_distribution()
+                .iter()
+                .map(|(l, c)| (*l, *c))
+                .collect::<Vec<_>>(),
+            vec![
+                (4, 2),
+                (5, 1),
+                (11, 1)
+            ]
+        );
+    }
+
+    // fn strict()
+    #[test]
+    fn strict_should_accept_a_clean_word_list(
+    ) {
+        let report =
+            WordListReport::of(
+                "golem größe cañón"
+                    .as_bytes(),
+            )
+            .unwrap();
+
+        assert!(report.is_clean());
+        assert!(report
+            .strict()
+            .is_ok());
+    }
+
+    #[test_case(
+        "golem CloudNative",
+        AppError::MixedCaseWord("CloudNative".to_string()) ;
+        "AppError::MixedCaseWord when a word is not all lowercase."
+    )]
+    #[test_case(
+        "golem web-assembly",
+        AppError::InvalidWord("web-assembly".to_string()) ;
+        "AppError::InvalidWord when a word has non-letter characters."
+    )]
+    #[test_case(
+        "golem wasm golem",
+        AppError::DuplicateWord("golem".to_string()) ;
+        "AppError::DuplicateWord when a word appears twice."
+    )]
+    #[test_case(
+        "  ",
+        AppError::NoWords ;
+        "AppError::NoWords when there are no words."
+    )]
+    fn strict_should_fail_on_the_first_problem(
+        input: &str,
+        expected: AppError,
+    ) {
+        let report =
+            WordListReport::of(
+                input.as_bytes(),
+            )
+            .unwrap();
+
+        let actual = report.strict();
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    // fn describe()
+    #[test]
+    fn describe_should_summarize_the_report(
+    ) {
+        let actual =
+            report().describe();
+        let expected = vec![
+            "Checked 8 entries: 4 accepted, 4 rejected.",
+            "Mixed case (2): CloudNative, Wasm",
+            "Not ASCII (1): café",
+            "Not letters (2): web-assembly, 42",
+            "Duplicates (2): golem, Wasm",
+            "Word lengths:",
+            "   4 letters: 2",
+            "   5 letters: 1",
+            "  11 letters: 1",
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
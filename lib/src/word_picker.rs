@@ -0,0 +1,417 @@
+use crate::{
+    app_error::{AppError, AppResult},
+    clock::Clock,
+    core::{
+        letter_count, normalized,
+        pick_word, word_by_id,
+        word_id_of, words_for,
+    },
+    daily::{
+        split_mix, DailySchedule,
+        GOLDEN_GAMMA,
+    },
+    game_config::GameConfig,
+};
+use error_stack::bail;
+use std::collections::HashSet;
+
+pub trait WordPicker {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str>;
+}
+
+/// Picks a word at random, differently on every run.
+#[derive(
+    Clone, Copy, Debug, Default,
+)]
+pub struct RandomPicker;
+
+impl WordPicker for RandomPicker {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str> {
+        pick_word(config)
+    }
+}
+
+/// Picks words at random, in the same order for the same seed, so
+/// that a run can be replayed. Built on the same SplitMix64 as the
+/// daily puzzle, so a seed keeps its words across `rand` versions.
+#[derive(Clone, Debug)]
+pub struct SeededPicker {
+    state: u64,
+}
+impl SeededPicker {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        let mixed =
+            split_mix(self.state);
+        self.state = self
+            .state
+            .wrapping_add(GOLDEN_GAMMA);
+
+        mixed
+    }
+}
+
+impl WordPicker for SeededPicker {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str> {
+        let candidates =
+            words_for(config)?;
+
+        let index = usize::try_from(
+            self.next()
+                % candidates.len()
+                    as u64,
+        )
+        .unwrap_or_default();
+
+        match candidates.get(index) {
+            Some(word) => Ok(word),
+            None => {
+                bail!(AppError::NoWords)
+            }
+        }
+    }
+}
+
+/// Picks today's daily puzzle word.
+pub struct DailyPicker<'a> {
+    schedule: DailySchedule,
+    clock: &'a dyn Clock,
+}
+impl<'a> DailyPicker<'a> {
+    pub fn new(
+        schedule: DailySchedule,
+        clock: &'a impl Clock,
+    ) -> Self {
+        Self { schedule, clock }
+    }
+}
+
+impl WordPicker for DailyPicker<'_> {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str> {
+        let puzzle =
+            self.schedule.puzzle_for(
+                self.clock.now(),
+                config,
+            )?;
+
+        Ok(puzzle.word())
+    }
+}
+
+/// Walks through the word list in order, and only starts over once
+/// every word allowed by the config has been picked.
+#[derive(Clone, Debug, Default)]
+pub struct SequentialPicker {
+    picked: HashSet<&'static str>,
+}
+impl SequentialPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WordPicker for SequentialPicker {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str> {
+        let candidates =
+            words_for(config)?;
+
+        let next = candidates
+            .iter()
+            .find(|w| {
+                !self
+                    .picked
+                    .contains(*w)
+            });
+
+        let word = match next {
+            Some(word) => *word,
+            None => {
+                for word in &candidates
+                {
+                    self.picked
+                        .remove(word);
+                }
+                candidates[0]
+            }
+        };
+
+        self.picked.insert(word);
+
+        Ok(word)
+    }
+}
+
+/// Always picks the same word.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedPicker {
+    word: &'static str,
+}
+impl FixedPicker {
+    pub fn new(
+        word: &'static str,
+    ) -> Self {
+        Self { word }
+    }
+
+    pub fn from_word_list(
+        word: &str,
+    ) -> AppResult<Self> {
+        let word = normalized(word);
+
+        match word_id_of(&word)
+            .and_then(word_by_id)
+        {
+            Some(word) => {
+                Ok(Self::new(word))
+            }
+            None => bail!(
+                AppError::UnknownWord(
+                    word
+                )
+            ),
+        }
+    }
+}
+
+impl WordPicker for FixedPicker {
+    fn pick(
+        &mut self,
+        config: &GameConfig,
+    ) -> AppResult<&'static str> {
+        if !config.allows_word_length(
+            letter_count(self.word),
+        ) {
+            bail!(AppError::NoWords)
+        }
+
+        Ok(self.word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_app_error,
+        clock::tests::TestClock,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn picks(
+        picker: &mut impl WordPicker,
+        config: &GameConfig,
+        count: usize,
+    ) -> Vec<&'static str> {
+        (0..count)
+            .map(|_| {
+                picker
+                    .pick(config)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    // SeededPicker
+    #[test]
+    fn seeded_picker_should_pick_the_same_words_for_the_same_seed(
+    ) {
+        let config =
+            GameConfig::default();
+
+        let first = picks(
+            &mut SeededPicker::new(42),
+            &config,
+            10,
+        );
+        let second = picks(
+            &mut SeededPicker::new(42),
+            &config,
+            10,
+        );
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn seeded_picker_should_keep_its_words_for_a_seed(
+    ) {
+        let actual = picks(
+            &mut SeededPicker::new(42),
+            &GameConfig::new(
+                6, 1, 32, false,
+            )
+            .unwrap(),
+            6,
+        );
+
+        assert_eq!(
+            actual,
+            vec![
+                "cloudnative",
+                "cloudnative",
+                "cloud",
+                "cloud",
+                "immortal",
+                "serverless"
+            ]
+        );
+    }
+
+    // DailyPicker
+    #[test]
+    fn daily_picker_should_pick_the_daily_puzzle_word(
+    ) {
+        let clock = TestClock::init(
+            2023, 10, 5, 12, 0,
+        );
+        let config =
+            GameConfig::default();
+        let schedule =
+            DailySchedule::default();
+
+        let actual = DailyPicker::new(
+            schedule.clone(),
+            &clock,
+        )
+        .pick(&config)
+        .unwrap();
+        let expected = schedule
+            .puzzle_for(
+                clock.now(),
+                &config,
+            )
+            .unwrap()
+            .word();
+
+        assert_eq!(actual, expected);
+    }
+
+    // SequentialPicker
+    #[test]
+    fn sequential_picker_should_not_repeat_a_word_until_the_pool_is_used_up(
+    ) {
+        let config =
+            GameConfig::default();
+        let pool_size =
+            words_for(&config)
+                .unwrap()
+                .len();
+
+        let mut picker =
+            SequentialPicker::new();
+        let first_round = picks(
+            &mut picker,
+            &config,
+            pool_size,
+        );
+        let second_round = picks(
+            &mut picker,
+            &config,
+            pool_size,
+        );
+
+        let distinct: HashSet<_> =
+            first_round
+                .iter()
+                .collect();
+
+        assert_eq!(
+            distinct.len(),
+            pool_size
+        );
+        assert_eq!(
+            first_round,
+            second_round
+        );
+    }
+
+    // FixedPicker
+    #[test]
+    fn fixed_picker_should_always_pick_its_word(
+    ) {
+        let actual = picks(
+            &mut FixedPicker::new(
+                "golem",
+            ),
+            &GameConfig::default(),
+            3,
+        );
+
+        assert_eq!(
+            actual,
+            vec!["golem"; 3]
+        );
+    }
+
+    #[test]
+    fn fixed_picker_should_fail_when_its_word_is_outside_the_configured_length_range(
+    ) {
+        let config = GameConfig::new(
+            6, 6, 8, false,
+        )
+        .unwrap();
+
+        let actual =
+            FixedPicker::new("golem")
+                .pick(&config);
+        let expected =
+            AppError::NoWords;
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn from_word_list_should_fail_when_the_word_is_not_in_the_word_list(
+    ) {
+        let actual =
+            FixedPicker::from_word_list(
+                "golem",
+            )
+            .map(|_| ());
+        let expected =
+            AppError::UnknownWord(
+                "golem".to_string(),
+            );
+
+        assert_app_error!(
+            actual, expected
+        );
+    }
+
+    #[test]
+    fn from_word_list_should_find_the_word_regardless_of_case(
+    ) {
+        let word =
+            word_by_id(0).unwrap();
+
+        let actual =
+            FixedPicker::from_word_list(
+                &word.to_uppercase(),
+            )
+            .unwrap()
+            .pick(&GameConfig::default())
+            .unwrap();
+
+        assert_eq!(actual, word);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{bindings::exports::golem::wordle::api::*, game, Component};
+use chrono::Duration;
+use lib::{
+    app_error::{AppError, AppResult, AppResultExt},
+    game_config,
+    game_state::GameState,
+    multi_game_state::MultiGameState,
+    share_grid,
+};
+
+fn no_game_in_progress() -> Vec<String> {
+    vec![
+        "Currently no game in progress. You can start a new game by using the `new-game` command."
+            .to_string(),
+    ]
+}
+
+fn or_no_game_in_progress(result: AppResult<Vec<String>>) -> GameResult {
+    match result {
+        Err(e) => match e.current_context() {
+            AppError::NoGameInProgress => Ok(no_game_in_progress()),
+            AppError::GameNotInProgress(id) => Ok(vec![format!(
+                "There is no game #{id} in progress. You can see your games by using the `list-games` command."
+            )]),
+            _ => Err(e.to_string()),
+        },
+        result => result.err_as_string(),
+    }
+}
+
+fn describe_game(id: u64, game_state: &GameState) -> Vec<String> {
+    let mut result = vec![format!("This is game #{id}.")];
+    result.extend(game_state.describe());
+    result
+}
+
+fn describe_multi_game(id: u64, multi_game_state: &MultiGameState) -> Vec<String> {
+    let mut result = vec![format!("This is game #{id}.")];
+    result.extend(multi_game_state.describe());
+    result
+}
+
+pub(crate) fn share_theme_from(theme: ShareTheme) -> share_grid::ShareTheme {
+    match theme {
+        ShareTheme::Light => share_grid::ShareTheme::Light,
+        ShareTheme::Dark => share_grid::ShareTheme::Dark,
+        ShareTheme::HighContrast => share_grid::ShareTheme::HighContrast,
+    }
+}
+
+pub(crate) fn game_mode_from(mode: GameMode) -> game_config::GameMode {
+    match mode {
+        GameMode::Classic => game_config::GameMode::Classic,
+        GameMode::Adversarial => game_config::GameMode::Adversarial,
+    }
+}
+
+pub(crate) fn game_config_from(config: GameConfig) -> AppResult<game_config::GameConfig> {
+    let seconds = |limit: Option<u32>| limit.map(|s| Duration::seconds(s.into()));
+
+    game_config::GameConfig::new(
+        config.max_attempts as usize,
+        config.min_word_length as usize,
+        config.max_word_length as usize,
+        config.hard_mode,
+    )?
+    .with_hint_budget(config.hint_budget as usize)
+    .with_mode(game_mode_from(config.mode))
+    .with_time_limits(
+        seconds(config.game_time_limit),
+        seconds(config.guess_time_limit),
+    )
+}
+
+impl Guest for Component {
+    fn new_game(mode: GameMode) -> GameResult {
+        let config = game_config::GameConfig::default().with_mode(game_mode_from(mode));
+
+        game::start_game(config, describe_game).err_as_string()
+    }
+
+    fn new_game_with_config(config: GameConfig) -> GameResult {
+        game_config_from(config)
+            .and_then(|config| game::start_game(config, describe_game))
+            .err_as_string()
+    }
+
+    fn new_daily_game() -> GameResult {
+        game::start_daily_game(describe_game).err_as_string()
+    }
+
+    fn continue_game(game_id: u64, player_guess: String) -> GameResult {
+        let result = game::play(game_id, &player_guess, |session_state, _, _| {
+            session_state.messages()
+        });
+
+        or_no_game_in_progress(result)
+    }
+
+    fn hint(game_id: u64) -> GameResult {
+        let result = game::hint(game_id, |hint, game_state| {
+            vec![
+                hint.to_string(),
+                format!("You have {} hints left.", game_state.hints_left()),
+            ]
+        });
+
+        or_no_game_in_progress(result)
+    }
+
+    fn suggest(game_id: u64) -> GameResult {
+        or_no_game_in_progress(game::suggest(game_id, |solver, game_state| {
+            solver.describe(game_state.attempts())
+        }))
+    }
+
+    fn game_status(game_id: u64) -> GameResult {
+        or_no_game_in_progress(game::status(game_id, describe_game))
+    }
+
+    fn list_games() -> GameResult {
+        Ok(game::list_games(|games| {
+            let lines: Vec<_> = games
+                .map(|(id, g)| {
+                    format!(
+                        "Game #{}: {} letters, {} of {} attempts used, last played on {}.",
+                        id,
+                        g.word_length(),
+                        g.attempts().len(),
+                        g.config().max_attempts(),
+                        g.last_update()
+                    )
+                })
+                .collect();
+
+            if lines.is_empty() {
+                no_game_in_progress()
+            } else {
+                lines
+            }
+        }))
+    }
+
+    fn abandon_game(game_id: u64) -> GameResult {
+        game::abandon_game(game_id)
+            .map(|()| vec![format!("Game #{game_id} has been abandoned.")])
+            .err_as_string()
+    }
+
+    fn create_challenge(word: String) -> GameResult {
+        game::create_challenge(&word, |challenge| {
+            vec![
+                format!("Your challenge code is '{}'.", challenge.code()),
+                "Friends can play your word by using the `new-game-from-challenge` command with this code, and you can see how they did by using the `challenge-results` command.".to_string(),
+            ]
+        })
+        .err_as_string()
+    }
+
+    fn new_game_from_challenge(code: String) -> GameResult {
+        game::start_challenge_game(&code, describe_game).err_as_string()
+    }
+
+    fn challenge_results(code: String) -> GameResult {
+        game::challenge_results(&code, |challenge| challenge.describe()).err_as_string()
+    }
+
+    fn join_race(player: String) -> GameResult {
+        game::join_race(&player, |race, game_state| {
+            vec![
+                format!(
+                    "{} joined race #{} for a {}-letter word. The first to guess it wins!",
+                    player.trim(),
+                    race.id(),
+                    race.word_length()
+                ),
+                format!("You have {} attempts.", game_state.attempts_left()),
+                "You can guess by using the `race-guess` command, and see how everyone is doing by using the `race-standings` command.".to_string(),
+            ]
+        })
+        .err_as_string()
+    }
+
+    fn race_guess(player: String, guess: String) -> GameResult {
+        game::race_guess(&player, &guess, |session_state, race, _| {
+            let mut lines = session_state.messages();
+
+            if race.winner() == Some(player.trim()) {
+                lines.push(format!("You won race #{}!", race.id()));
+            }
+
+            lines
+        })
+        .err_as_string()
+    }
+
+    fn race_standings() -> GameResult {
+        game::race_standings(|race| race.describe_standings()).err_as_string()
+    }
+
+    fn new_multi_game(boards: u32) -> GameResult {
+        game::start_multi_game(boards as usize, describe_multi_game).err_as_string()
+    }
+
+    fn continue_multi_game(game_id: u64, player_guess: String) -> GameResult {
+        let result = game::play_multi(game_id, &player_guess, |session_state, _, _| {
+            session_state.messages()
+        });
+
+        or_no_game_in_progress(result)
+    }
+
+    fn multi_game_status(game_id: u64) -> GameResult {
+        or_no_game_in_progress(game::multi_status(game_id, describe_multi_game))
+    }
+
+    fn stats() -> GameResult {
+        Ok(game::stats(|s| s.describe()))
+    }
+
+    fn history() -> GameResult {
+        Ok(game::history(|records| {
+            records.map(|r| r.describe()).collect()
+        }))
+    }
+
+    fn replay(game_id: u64) -> GameResult {
+        game::replay(game_id)
+            .map(|steps| {
+                steps
+                    .iter()
+                    .flat_map(|step| {
+                        let mut lines = vec![format!("> {}", step.guess())];
+                        lines.extend(step.session_state().messages());
+                        lines
+                    })
+                    .collect()
+            })
+            .err_as_string()
+    }
+
+    fn share(game_id: u64, theme: ShareTheme) -> GameResult {
+        game::share(game_id, share_theme_from(theme)).err_as_string()
+    }
+
+    fn install_word_list(words: String) -> GameResult {
+        game::install_word_list(words)
+            .map(|count| vec![format!("Installed a word list of {count} words.")])
+            .err_as_string()
+    }
+}
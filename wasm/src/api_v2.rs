@@ -0,0 +1,427 @@
+use crate::{
+    api::{game_config_from, game_mode_from, share_theme_from},
+    bindings::exports::golem::wordle::api_v2::*,
+    game, Component,
+};
+use lib::{
+    app_error::{AppError, AppResult},
+    challenge,
+    char_result::CharResult,
+    core, game_config,
+    game_history::{self, GameOutcome},
+    game_state::GameState,
+    hard_mode, hint,
+    keyboard::{self, Keyboard},
+    multi_game_state::{MultiGameState, MultiSessionState},
+    player_stats, race,
+    session_state::SessionState,
+    solver::SUGGESTION_COUNT,
+};
+
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+fn tile_from(char_result: &CharResult) -> Tile {
+    let result = match char_result.result() {
+        core::GuessResult::Correct => GuessResult::Correct,
+        core::GuessResult::Present => GuessResult::Present,
+        core::GuessResult::Absent => GuessResult::Absent,
+    };
+
+    Tile {
+        letter: char_result.letter().to_string(),
+        result,
+    }
+}
+
+pub(crate) fn tiles_from(attempt: &[CharResult]) -> Vec<Tile> {
+    attempt.iter().map(tile_from).collect()
+}
+
+fn keyboard_from(keyboard: &Keyboard) -> Vec<LetterStatus> {
+    keyboard
+        .letters()
+        .map(|(letter, state)| LetterStatus {
+            letter: letter.to_string(),
+            state: match state {
+                keyboard::LetterState::Unknown => LetterState::Unknown,
+                keyboard::LetterState::Absent => LetterState::Absent,
+                keyboard::LetterState::Present => LetterState::Present,
+                keyboard::LetterState::Correct => LetterState::Correct,
+            },
+        })
+        .collect()
+}
+
+pub(crate) fn snapshot_from(id: u64, game_state: &GameState) -> GameSnapshot {
+    GameSnapshot {
+        id,
+        word_length: to_u32(game_state.word_length()),
+        attempts: game_state
+            .attempts()
+            .iter()
+            .map(|a| tiles_from(a))
+            .collect(),
+        attempts_left: to_u32(game_state.attempts_left()),
+        started_at: game_state.started_at().timestamp_millis(),
+        last_update: game_state.last_update().timestamp_millis(),
+        puzzle_number: game_state.puzzle_number().map(to_u32),
+        keyboard: keyboard_from(game_state.keyboard()),
+        hints_left: to_u32(game_state.hints_left()),
+        deadline: game_state.deadline().map(|d| d.timestamp_millis()),
+        candidates: game_state.candidate_count().map(to_u32),
+    }
+}
+
+/// The word of a lost game is empty while it is still to be kept
+/// secret, as for a player out of a race that is still on.
+fn guess_outcome_from(
+    session_state: SessionState,
+    snapshot: GameSnapshot,
+    revealed_word: Option<&str>,
+) -> GuessOutcome {
+    let lost_game = |snapshot| LostGame {
+        word: revealed_word.unwrap_or_default().to_string(),
+        snapshot,
+    };
+
+    match session_state {
+        SessionState::InProgress { .. } => GuessOutcome::InProgress(snapshot),
+        SessionState::Won(_) => GuessOutcome::Won(snapshot),
+        SessionState::Lost(_) => GuessOutcome::Lost(lost_game(snapshot)),
+        SessionState::TimedOut(_) => GuessOutcome::TimedOut(lost_game(snapshot)),
+    }
+}
+
+/// A board's word is only told once it is solved or the game is over.
+fn multi_snapshot_from(id: u64, multi_game_state: &MultiGameState) -> MultiGameSnapshot {
+    MultiGameSnapshot {
+        id,
+        word_length: to_u32(multi_game_state.word_length()),
+        attempts_left: to_u32(multi_game_state.attempts_left()),
+        started_at: multi_game_state.started_at().timestamp_millis(),
+        last_update: multi_game_state.last_update().timestamp_millis(),
+        boards: multi_game_state
+            .boards()
+            .iter()
+            .map(|board| BoardRow {
+                attempts: board.attempts().iter().map(|a| tiles_from(a)).collect(),
+                solved: board.is_won(),
+                word: board.revealed_word().map(str::to_string),
+            })
+            .collect(),
+    }
+}
+
+fn multi_guess_outcome_from(
+    session_state: &MultiSessionState,
+    snapshot: MultiGameSnapshot,
+) -> MultiGuessOutcome {
+    match session_state.overall() {
+        SessionState::InProgress { .. } => MultiGuessOutcome::InProgress(snapshot),
+        SessionState::Won(_) => MultiGuessOutcome::Won(snapshot),
+        SessionState::Lost(_) => MultiGuessOutcome::Lost(snapshot),
+        SessionState::TimedOut(_) => MultiGuessOutcome::TimedOut(snapshot),
+    }
+}
+
+fn race_scoreboard_from(race: &race::Race) -> RaceScoreboard {
+    RaceScoreboard {
+        id: race.id(),
+        word_length: to_u32(race.word_length()),
+        started_at: race.started_at().timestamp_millis(),
+        winner: race.winner().map(str::to_string),
+        word: race.revealed_word().map(str::to_string),
+        standings: race
+            .standings()
+            .iter()
+            .map(|s| RaceStanding {
+                rank: to_u32(s.rank()),
+                player: s.player().to_string(),
+                status: match s.status() {
+                    race::RacerStatus::Solved => RacerStatus::Solved,
+                    race::RacerStatus::Playing => RacerStatus::Playing,
+                    race::RacerStatus::Out => RacerStatus::Out,
+                },
+                guesses: to_u32(s.guesses()),
+                time: u32::try_from(s.time().num_seconds()).unwrap_or(u32::MAX),
+            })
+            .collect(),
+    }
+}
+
+fn challenge_result_from(result: &challenge::ChallengeResult) -> ChallengeResult {
+    ChallengeResult {
+        game_id: result.game_id(),
+        won: result.outcome() == GameOutcome::Won,
+        timed_out: result.outcome() == GameOutcome::TimedOut,
+        guesses: to_u32(result.guesses()),
+        hints: to_u32(result.hints()),
+        time: u32::try_from(result.time().num_seconds()).unwrap_or(u32::MAX),
+        ended_at: result.ended_at().timestamp_millis(),
+    }
+}
+
+fn stats_from(stats: &player_stats::PlayerStats) -> PlayerStats {
+    PlayerStats {
+        played: to_u32(stats.played()),
+        won: to_u32(stats.won()),
+        win_percentage: to_u32(stats.win_percentage()),
+        current_streak: to_u32(stats.current_streak()),
+        max_streak: to_u32(stats.max_streak()),
+        guess_distribution: stats
+            .guess_distribution()
+            .iter()
+            .copied()
+            .map(to_u32)
+            .collect(),
+        hinted_wins: to_u32(stats.hinted_wins()),
+        timed_out: to_u32(stats.timed_out()),
+        fastest_timed_win: stats
+            .fastest_timed_win()
+            .map(|t| u32::try_from(t.num_seconds()).unwrap_or(u32::MAX)),
+    }
+}
+
+fn game_record_from(record: &game_history::GameRecord) -> GameRecord {
+    GameRecord {
+        id: record.id(),
+        word: record.word().to_string(),
+        attempts: record.attempts().iter().map(|a| tiles_from(a)).collect(),
+        started_at: record.started_at().timestamp_millis(),
+        ended_at: record.ended_at().timestamp_millis(),
+        won: record.outcome() == GameOutcome::Won,
+        timed_out: record.outcome() == GameOutcome::TimedOut,
+        hints: record.hints().iter().map(|h| hint_from(h.hint())).collect(),
+    }
+}
+
+fn replay_step_from(step: &game_history::ReplayStep) -> ReplayStep {
+    ReplayStep {
+        guess: step.guess().to_string(),
+        messages: step.session_state().messages(),
+    }
+}
+
+fn hint_from(hint: &hint::Hint) -> Hint {
+    match hint {
+        hint::Hint::CorrectLetter { letter, position } => Hint::CorrectLetter(LetterPosition {
+            letter: letter.clone(),
+            position: to_u32(*position),
+        }),
+        hint::Hint::AbsentLetter { letter } => Hint::AbsentLetter(letter.clone()),
+    }
+}
+
+fn hard_mode_rule_from(rule: &hard_mode::HardModeRule) -> HardModeRule {
+    match rule {
+        hard_mode::HardModeRule::KeepCorrectLetter { letter, position } => {
+            HardModeRule::KeepCorrectLetter(LetterPosition {
+                letter: letter.clone(),
+                position: to_u32(*position),
+            })
+        }
+        hard_mode::HardModeRule::ReusePresentLetter { letter, count } => {
+            HardModeRule::ReusePresentLetter(LetterCount {
+                letter: letter.clone(),
+                count: to_u32(*count),
+            })
+        }
+    }
+}
+
+fn error_from(error: &AppError) -> Error {
+    match error {
+        AppError::NoWords => Error::NoWords,
+        AppError::StdIoRead => Error::StdIoRead,
+        AppError::InvalidCharset => Error::InvalidCharset,
+        AppError::InvalidGuessLength(len) => Error::InvalidGuessLength(to_u32(*len)),
+        AppError::UnknownWord(guess) => Error::UnknownWord(guess.clone()),
+        AppError::InvalidGameConfig(reason) => Error::InvalidGameConfig(reason.clone()),
+        AppError::HardModeViolation(rule) => Error::HardModeViolation(hard_mode_rule_from(rule)),
+        AppError::NoGameInProgress => Error::NoGameInProgress,
+        AppError::InvalidSnapshot(reason) => Error::InvalidSnapshot(reason.clone()),
+        AppError::SnapshotEncode => Error::SnapshotEncode,
+        AppError::SnapshotDecode => Error::SnapshotDecode,
+        AppError::FileRead(path) => Error::FileRead(path.clone()),
+        AppError::FileWrite(path) => Error::FileWrite(path.clone()),
+        AppError::DailyPuzzleNotStarted(epoch) => Error::DailyPuzzleNotStarted(epoch.clone()),
+        AppError::MixedCaseWord(word) => Error::MixedCaseWord(word.clone()),
+        AppError::InvalidWord(word) => Error::InvalidWord(word.clone()),
+        AppError::DuplicateWord(word) => Error::DuplicateWord(word.clone()),
+        AppError::StatsEncode => Error::StatsEncode,
+        AppError::StatsDecode => Error::StatsDecode,
+        AppError::GameNotFound(id) => Error::GameNotFound(*id),
+        AppError::NoFinishedGame => Error::NoFinishedGame,
+        AppError::NoHintsLeft(budget) => Error::NoHintsLeft(to_u32(*budget)),
+        AppError::NoHintAvailable => Error::NoHintAvailable,
+        AppError::UnsolvedWord(word) => Error::UnsolvedWord(word.clone()),
+        AppError::GameNotInProgress(id) => Error::GameNotInProgress(*id),
+        AppError::TooManyGames(max_games) => Error::TooManyGames(to_u32(*max_games)),
+        AppError::InvalidGameLimits(reason) => Error::InvalidGameLimits(reason.clone()),
+        AppError::NoRaceInProgress => Error::NoRaceInProgress,
+        AppError::RaceOver => Error::RaceOver,
+        AppError::InvalidPlayerName => Error::InvalidPlayerName,
+        AppError::PlayerAlreadyInRace(name) => Error::PlayerAlreadyInRace(name.clone()),
+        AppError::PlayerNotInRace(name) => Error::PlayerNotInRace(name.clone()),
+        AppError::ChallengeNotFound(code) => Error::ChallengeNotFound(code.clone()),
+    }
+}
+
+pub(crate) fn typed<T>(result: AppResult<T>) -> Result<T, Error> {
+    result.map_err(|e| error_from(e.current_context()))
+}
+
+impl Guest for Component {
+    fn new_game(mode: GameMode) -> Result<GameSnapshot, Error> {
+        typed(game::start_game(
+            game_config::GameConfig::default().with_mode(game_mode_from(mode)),
+            snapshot_from,
+        ))
+    }
+
+    fn new_game_with_config(config: GameConfig) -> Result<GameSnapshot, Error> {
+        typed(game_config_from(config).and_then(|config| game::start_game(config, snapshot_from)))
+    }
+
+    fn new_daily_game() -> Result<GameSnapshot, Error> {
+        typed(game::start_daily_game(snapshot_from))
+    }
+
+    fn continue_game(game_id: u64, guess: String) -> Result<GuessOutcome, Error> {
+        typed(game::play(
+            game_id,
+            &guess,
+            |session_state, id, game_state| {
+                guess_outcome_from(
+                    session_state,
+                    snapshot_from(id, game_state),
+                    game_state.revealed_word(),
+                )
+            },
+        ))
+    }
+
+    fn hint(game_id: u64) -> Result<Hint, Error> {
+        typed(game::hint(game_id, |hint, _| hint_from(&hint)))
+    }
+
+    fn suggest(game_id: u64) -> Result<Assist, Error> {
+        typed(game::suggest(game_id, |solver, game_state| Assist {
+            candidate_count: to_u32(solver.candidates(game_state.attempts()).len()),
+            suggestions: solver
+                .rank(game_state.attempts())
+                .iter()
+                .take(SUGGESTION_COUNT)
+                .map(|s| Suggestion {
+                    word: s.word().to_string(),
+                    entropy: s.entropy(),
+                    candidate: s.is_candidate(),
+                })
+                .collect(),
+        }))
+    }
+
+    fn game_status(game_id: u64) -> Result<GameSnapshot, Error> {
+        typed(game::status(game_id, snapshot_from))
+    }
+
+    fn list_games() -> Vec<GameSnapshot> {
+        game::list_games(|games| games.map(|(id, g)| snapshot_from(id, g)).collect())
+    }
+
+    fn abandon_game(game_id: u64) -> Result<(), Error> {
+        typed(game::abandon_game(game_id))
+    }
+
+    fn save_game(game_id: u64) -> Result<Vec<u8>, Error> {
+        typed(game::save_game(game_id))
+    }
+
+    fn load_game(data: Vec<u8>) -> Result<GameSnapshot, Error> {
+        typed(game::load_game(&data, snapshot_from))
+    }
+
+    fn create_challenge(word: String) -> Result<String, Error> {
+        typed(game::create_challenge(&word, |challenge| {
+            challenge.code().to_string()
+        }))
+    }
+
+    fn new_game_from_challenge(code: String) -> Result<GameSnapshot, Error> {
+        typed(game::start_challenge_game(&code, snapshot_from))
+    }
+
+    fn challenge_results(code: String) -> Result<Vec<ChallengeResult>, Error> {
+        typed(game::challenge_results(&code, |challenge| {
+            challenge
+                .results()
+                .iter()
+                .map(challenge_result_from)
+                .collect()
+        }))
+    }
+
+    fn join_race(player: String) -> Result<GameSnapshot, Error> {
+        typed(game::join_race(&player, |race, game_state| {
+            snapshot_from(race.id(), game_state)
+        }))
+    }
+
+    fn race_guess(player: String, guess: String) -> Result<GuessOutcome, Error> {
+        typed(game::race_guess(
+            &player,
+            &guess,
+            |session_state, race, game_state| {
+                guess_outcome_from(
+                    session_state,
+                    snapshot_from(race.id(), game_state),
+                    race.revealed_word(),
+                )
+            },
+        ))
+    }
+
+    fn race_standings() -> Result<RaceScoreboard, Error> {
+        typed(game::race_standings(race_scoreboard_from))
+    }
+
+    fn new_multi_game(boards: u32) -> Result<MultiGameSnapshot, Error> {
+        typed(game::start_multi_game(boards as usize, multi_snapshot_from))
+    }
+
+    fn continue_multi_game(game_id: u64, guess: String) -> Result<MultiGuessOutcome, Error> {
+        typed(game::play_multi(
+            game_id,
+            &guess,
+            |session_state, id, multi_game_state| {
+                multi_guess_outcome_from(&session_state, multi_snapshot_from(id, multi_game_state))
+            },
+        ))
+    }
+
+    fn multi_game_status(game_id: u64) -> Result<MultiGameSnapshot, Error> {
+        typed(game::multi_status(game_id, multi_snapshot_from))
+    }
+
+    fn stats() -> PlayerStats {
+        game::stats(stats_from)
+    }
+
+    fn history() -> Vec<GameRecord> {
+        game::history(|records| records.map(game_record_from).collect())
+    }
+
+    fn replay(game_id: u64) -> Result<Vec<ReplayStep>, Error> {
+        typed(game::replay(game_id).map(|steps| steps.iter().map(replay_step_from).collect()))
+    }
+
+    fn share(game_id: u64, theme: ShareTheme) -> Result<Vec<String>, Error> {
+        typed(game::share(game_id, share_theme_from(theme)))
+    }
+
+    fn install_word_list(words: String) -> Result<u32, Error> {
+        typed(game::install_word_list(words).map(to_u32))
+    }
+}
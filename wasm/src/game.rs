@@ -0,0 +1,241 @@
+use lib::{
+    app_error::AppResult,
+    app_state::AppState,
+    challenge::Challenge,
+    clock::RealClock,
+    core::with_app_state,
+    daily::DailySchedule,
+    game_config::{GameConfig, DEFAULT_MAX_WORD_LENGTH, DEFAULT_MIN_WORD_LENGTH},
+    game_history::{GameRecord, ReplayStep},
+    game_snapshot::GameSnapshot,
+    game_state::GameState,
+    hint::Hint,
+    multi_game_state::{default_max_attempts, MultiGameState, MultiSessionState},
+    player_stats::PlayerStats,
+    race::Race,
+    session_state::SessionState,
+    share_grid::{self, ShareTheme},
+    solver::Solver,
+    word_list::{self, WordListSource},
+    word_picker::RandomPicker,
+};
+
+/// Runs `f` on the app state. A word list named by the environment that
+/// cannot be used fails the first call, then the compiled-in list is
+/// played.
+fn with_checked_app_state<T>(
+    f: impl FnOnce(&mut AppState<'static>) -> AppResult<T>,
+) -> AppResult<T> {
+    word_list::check_env()?;
+
+    with_app_state(f)
+}
+
+pub(crate) fn start_game<T>(
+    config: GameConfig,
+    f: impl FnOnce(u64, &GameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let (id, game_state) = state.new_game_with(&mut RandomPicker, config, &RealClock)?;
+
+        Ok(f(id, game_state))
+    })
+}
+
+pub(crate) fn start_daily_game<T>(f: impl FnOnce(u64, &GameState) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let (id, game_state) = state.new_daily_game_with(
+            &DailySchedule::from_env(),
+            GameConfig::default(),
+            &RealClock,
+        )?;
+
+        Ok(f(id, game_state))
+    })
+}
+
+pub(crate) fn play<T>(
+    game_id: u64,
+    guess: &str,
+    f: impl FnOnce(SessionState, u64, &GameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let game_state = state.game_as_mut(game_id)?;
+
+        let session_state = SessionState::determined_by(guess.trim(), game_state)?;
+        let is_over = !matches!(session_state, SessionState::InProgress { .. });
+
+        let result = f(session_state, game_id, game_state);
+
+        if is_over {
+            state.end_game(game_id);
+        }
+
+        Ok(result)
+    })
+}
+
+pub(crate) fn hint<T>(game_id: u64, f: impl FnOnce(Hint, &GameState) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let game_state = state.game_as_mut(game_id)?;
+
+        let hint = game_state.hint()?;
+
+        Ok(f(hint, game_state))
+    })
+}
+
+pub(crate) fn suggest<T>(game_id: u64, f: impl FnOnce(&Solver, &GameState) -> T) -> AppResult<T> {
+    status(game_id, |_, game_state| {
+        f(&Solver::new(game_state.word_length()), game_state)
+    })
+}
+
+pub(crate) fn save_game(game_id: u64) -> AppResult<Vec<u8>> {
+    status(game_id, |_, game_state| GameSnapshot::of(game_state))??.to_bytes()
+}
+
+pub(crate) fn load_game<T>(bytes: &[u8], f: impl FnOnce(u64, &GameState) -> T) -> AppResult<T> {
+    let snapshot = GameSnapshot::from_bytes(bytes)?;
+
+    with_checked_app_state(|state| {
+        let (id, game_state) = state.restore_game_from(&snapshot, &RealClock)?;
+
+        Ok(f(id, game_state))
+    })
+}
+
+/// The games in progress, once the stale ones are dropped.
+pub(crate) fn list_games<T>(f: impl FnOnce(&mut dyn Iterator<Item = (u64, &GameState)>) -> T) -> T {
+    with_app_state(|state| {
+        state.drop_stale_games();
+
+        f(&mut state.games())
+    })
+}
+
+pub(crate) fn abandon_game(game_id: u64) -> AppResult<()> {
+    with_checked_app_state(|state| state.abandon_game(game_id))
+}
+
+pub(crate) fn create_challenge<T>(word: &str, f: impl FnOnce(&Challenge) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| Ok(f(state.create_challenge(word, &RealClock)?)))
+}
+
+pub(crate) fn start_challenge_game<T>(
+    code: &str,
+    f: impl FnOnce(u64, &GameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let (id, game_state) = state.new_challenge_game(code, GameConfig::default(), &RealClock)?;
+
+        Ok(f(id, game_state))
+    })
+}
+
+pub(crate) fn challenge_results<T>(code: &str, f: impl FnOnce(&Challenge) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| Ok(f(state.challenge(code, &RealClock)?)))
+}
+
+/// Joins the race in progress, or starts a new one.
+pub(crate) fn join_race<T>(player: &str, f: impl FnOnce(&Race, &GameState) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let race =
+            state.join_race_with(player, &mut RandomPicker, GameConfig::default(), &RealClock)?;
+
+        Ok(f(race, race.player(player)?))
+    })
+}
+
+pub(crate) fn race_guess<T>(
+    player: &str,
+    guess: &str,
+    f: impl FnOnce(SessionState, &Race, &GameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let race = state.race_as_mut()?;
+
+        let session_state = race.guess(player, guess.trim())?;
+
+        Ok(f(session_state, race, race.player(player)?))
+    })
+}
+
+pub(crate) fn race_standings<T>(f: impl FnOnce(&Race) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| Ok(f(state.race()?)))
+}
+
+pub(crate) fn start_multi_game<T>(
+    boards: usize,
+    f: impl FnOnce(u64, &MultiGameState) -> T,
+) -> AppResult<T> {
+    let config = GameConfig::new(
+        default_max_attempts(boards),
+        DEFAULT_MIN_WORD_LENGTH,
+        DEFAULT_MAX_WORD_LENGTH,
+        false,
+    )?;
+
+    with_checked_app_state(|state| {
+        let (id, multi_game_state) =
+            state.new_multi_game_with(&mut RandomPicker, boards, config, &RealClock)?;
+
+        Ok(f(id, multi_game_state))
+    })
+}
+
+pub(crate) fn play_multi<T>(
+    game_id: u64,
+    guess: &str,
+    f: impl FnOnce(MultiSessionState, u64, &MultiGameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| {
+        let multi_game_state = state.multi_game_as_mut(game_id)?;
+
+        let session_state = multi_game_state.guess(guess.trim())?;
+        let is_over = session_state.is_over();
+
+        let result = f(session_state, game_id, multi_game_state);
+
+        if is_over {
+            state.end_multi_game(game_id);
+        }
+
+        Ok(result)
+    })
+}
+
+pub(crate) fn multi_status<T>(
+    game_id: u64,
+    f: impl FnOnce(u64, &MultiGameState) -> T,
+) -> AppResult<T> {
+    with_checked_app_state(|state| Ok(f(game_id, state.multi_game(game_id)?)))
+}
+
+pub(crate) fn install_word_list(words: String) -> AppResult<usize> {
+    word_list::install_from(&WordListSource::Text(words))
+}
+
+pub(crate) fn stats<T>(f: impl FnOnce(&PlayerStats) -> T) -> T {
+    with_app_state(|state| f(state.stats()))
+}
+
+pub(crate) fn history<T>(f: impl FnOnce(&mut dyn Iterator<Item = &GameRecord>) -> T) -> T {
+    with_app_state(|state| f(&mut state.history().games()))
+}
+
+pub(crate) fn replay(game_id: u64) -> AppResult<Vec<ReplayStep>> {
+    with_checked_app_state(|state| state.replay(game_id))
+}
+
+pub(crate) fn share(game_id: u64, theme: ShareTheme) -> AppResult<Vec<String>> {
+    with_checked_app_state(|state| {
+        let record = state.history().game(game_id)?;
+
+        Ok(share_grid::share_grid(record, theme))
+    })
+}
+
+pub(crate) fn status<T>(game_id: u64, f: impl FnOnce(u64, &GameState) -> T) -> AppResult<T> {
+    with_checked_app_state(|state| Ok(f(game_id, state.game(game_id)?)))
+}